@@ -0,0 +1,542 @@
+// 命令行参数解析（不依赖第三方库）
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::clock;
+use crate::generator::{ArrivalDist, ServiceDist, WorkloadSpec};
+use crate::loader::Format;
+use crate::memory::{Fit, MemoryModel};
+use crate::scheduler::{Algorithm, ChannelTie, SchedulerConfig, TieBreak, TimeMode};
+
+pub const USAGE: &str = "\
+用法：
+  lab1 [demo [作业流A] [作业流B]]       运行固定的演示实验（缺省使用内置样例）
+  lab1 run [选项]                       用一种算法调度一个作业流
+  lab1 compare [选项]                   比较多种算法、多种道数下的平均指标
+  lab1 experiment [选项]                在一批随机作业流上比较算法，给出均值、标准差与 95% 置信区间
+  lab1 generate [选项]                  输出内置样例或随机生成的作业流文件
+  lab1 help                             显示本帮助
+
+run / compare 选项：
+  -a, --algorithm <名称[,名称...]>      调度算法：fcfs | sjf | hrrn | srtf | rr | mlfq | prio | pprio（compare 缺省为以上全部）|
+                                        实时调度 edf（抢占）| npedf（非抢占）| rm（单调速率）|
+                                        比例份额 lottery（彩票）| stride（步幅），按作业的 tickets 字段分配 CPU
+  -m, --channels <m[,m...]>             道数（CPU 数；两级调度时为多道程序度），compare 可给出多个，缺省 1
+  -q, --quantum <q[,q...]>              RR/MLFQ/LOTTERY/STRIDE 的时间片，compare 可给出多个，缺省 2
+      --switch-cost <c>                 上下文切换开销，缺省 0
+      --levels <n>                      MLFQ 级数，缺省 3（第 i 级时间片为 q × 2^i）
+      --level-quanta <q0,q1,...>        MLFQ 各级时间片，给出时覆盖 --levels
+      --boost <s>                       MLFQ 每隔 s 把所有作业提升回 0 级，缺省不提升
+      --aging <s>                       优先级调度（prio/pprio）每隔 s 把已在就绪队列中等待满 s 的作业优先数减 1，
+                                        被抢占回到就绪队列时重新计时，缺省不老化
+      --lottery-seed <整数>             彩票调度的抽签种子，相同种子得到相同的调度，缺省 0
+      --horizon <t>                     带 period 的周期作业展开到 t 时刻为止，缺省为超周期（周期的最小公倍数）
+      --ticks <n>                       精确模式：以 1/n 分钟为刻度按整数计算（整数分钟的习题用 1，
+                                        两位小数用 100），作业时间与时间片、切换开销、提升/老化周期须落在刻度上；
+                                        缺省按浮点计算
+      --tie <规则>                      关键字（到达时间、运行时间、响应比等）相等时的取舍：
+                                        fifo（先进入就绪队列者，缺省）| id（id 小者）| arrival（到达早者）|
+                                        random[:种子]；RR 与 MLFQ 的同级队列始终先进先出，
+                                        抢占时运行中的作业在相等时保留道
+      --channel-tie <lowest|random[:种子]>
+                                        多条道同时空闲时的分派顺序：编号小者优先（缺省）或随机
+      --memory <容量>                   主存总容量（与作业的 memory 字段同一单位）：作业到达后先进后备队列，
+                                        作业调度按所选算法从放得下的作业中挑选、分配分区后才进入就绪队列；
+                                        缺省不考虑主存
+      --fit <first|best|worst>          可变分区的分配算法：首次适应（缺省）| 最佳适应 | 最坏适应
+      --job-scheduler <fcfs|sjf|hrrn|prio>
+                                        两级调度（教材的多道程序模型）：-m 改为多道程序度，只有一个 CPU；
+                                        作业调度用该算法把作业调入系统，使其中至多 m 个作业，
+                                        -a 给出的算法作为进程调度在 CPU 上分时运行它们。缺省 -m 为 CPU 数
+  -i, --input <文件>                    作业流文件（.csv/.toml/.json），缺省为内置样例 A；
+                                        到达时间可写成时钟时刻（如 8:50），结果随之按时刻显示
+                                        可选 deadline（绝对截止时间）与 period（周期作业的周期）字段用于实时调度，
+                                        tickets（彩票数，正整数，缺省 1）用于比例份额调度
+      --clock <H:MM>                    把 0 时刻对应到该时钟时刻，按时刻显示到达/开始/结束时间；
+                                        作业流本身用时钟时刻时，以此代替最早到达时刻作为起点
+  -f, --format <table|csv|json|latex>   输出格式，缺省 table；run 的 csv 为逐作业结果，
+                                        compare 的 csv 为每次运行的汇总指标
+  -e, --export <目录>                   把每次运行的完整结果写入目录（JSON、逐作业/时间线/汇总 CSV、LaTeX 表格）
+  -g, --gantt                           在结果表后输出 ASCII 甘特图
+      --starvation <s>                  在结果表中标出等待时间超过 s 的作业（饥饿），缺省不标出
+      --svg <文件|目录>                  输出 SVG 甘特图（compare 时为目录，每次运行一个文件）
+      --tex <文件|目录>                  输出报告用的 LaTeX tabular（compare 时为目录，每次运行一个文件）
+      --trace <文件>                    （仅 run）把每个调度决策（到达、调入、进入就绪队列、分派、时间片用完、
+                                        抢占、完成、道忙/闲）连同当时的就绪队列按 JSON lines 写入文件
+      --explain                         （仅 run，表格输出）在结果表前逐个时刻讲解调度过程：每次分派列出
+                                        候选作业的等待时间与挑选依据（响应比、运行时间等），说明选中谁、为什么
+
+experiment 选项：
+  -a, -m, -q 及策略参数同 compare；-f 为 table | csv | json | latex（汇总表）
+  -w, --workloads <N>                   随机作业流个数，缺省 30；第 k 个作业流的种子为 --seed + k
+  -n, --seed, --arrival, --service, --decimals
+                                        每个作业流的生成参数，同 generate
+
+generate 选项：
+  -s, --sample <a|b>                    内置样例作业流，缺省 a
+  -n, --jobs <n>                        随机生成 n 个作业（给出任一随机选项即改为随机生成，缺省 20 个）
+      --seed <整数>                     随机种子，相同种子得到相同作业流，缺省 1
+      --arrival <分布>                  到达间隔：exp:<均值> | uniform:<最小>,<最大> |
+                                        bursty:<突发平均规模>,<突发间隔均值>,<突发内间隔均值>，缺省 exp:2
+      --service <分布>                  运行时间：exp:<均值> | uniform:<最小>,<最大> |
+                                        bimodal:<短>,<长>,<短作业比例> | pareto:<最小值>,<形状>，缺省 exp:4
+      --decimals <d>                    时间保留的小数位数，缺省 2
+  -f, --format <csv|toml|json>          输出格式，缺省由 -o 的扩展名决定，否则为 csv
+  -o, --output <文件>                   输出文件，缺省写到标准输出
+";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Csv,
+    Json,
+    Latex,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sample {
+    A,
+    B,
+}
+
+#[derive(Debug)]
+pub struct RunOpts {
+    pub algorithm: Algorithm,
+    pub config: SchedulerConfig,
+    pub channels: usize,
+    pub input: Option<PathBuf>,
+    pub output: OutputFormat,
+    pub gantt: bool,
+    pub starvation: Option<f64>, // 饥饿判定阈值（等待时间）
+    pub clock: Option<f64>,      // 0 时刻对应的时钟时刻（零点起的分钟数）
+    pub svg: Option<PathBuf>,
+    pub tex: Option<PathBuf>,
+    pub export: Option<PathBuf>,
+    pub trace: Option<PathBuf>, // 事件日志（JSON lines）
+    pub explain: bool,
+}
+
+#[derive(Debug)]
+pub struct CompareOpts {
+    pub algorithms: Vec<Algorithm>,
+    pub config: SchedulerConfig,
+    pub quanta: Vec<f64>, // 使用时间片的算法对每个时间片各运行一次
+    pub channels: Vec<usize>,
+    pub input: Option<PathBuf>,
+    pub output: OutputFormat,
+    pub gantt: bool,
+    pub starvation: Option<f64>,
+    pub clock: Option<f64>,
+    pub svg_dir: Option<PathBuf>,
+    pub tex_dir: Option<PathBuf>,
+    pub export: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct ExperimentOpts {
+    pub algorithms: Vec<Algorithm>,
+    pub config: SchedulerConfig,
+    pub quanta: Vec<f64>,
+    pub channels: Vec<usize>,
+    pub workload: WorkloadSpec,
+    pub workloads: usize,
+    pub output: OutputFormat,
+}
+
+// generate 的作业流来源
+#[derive(Debug)]
+pub enum Workload {
+    Sample(Sample),
+    Random(WorkloadSpec),
+}
+
+#[derive(Debug)]
+pub struct GenerateOpts {
+    pub workload: Workload,
+    pub format: Format,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Demo { stream_a: Option<PathBuf>, stream_b: Option<PathBuf> },
+    Run(RunOpts),
+    Compare(CompareOpts),
+    Experiment(ExperimentOpts),
+    Generate(GenerateOpts),
+    Help,
+}
+
+#[derive(Debug)]
+pub struct CliError(pub String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn err<T>(msg: impl Into<String>) -> Result<T, CliError> {
+    Err(CliError(msg.into()))
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, CliError> {
+    let Some((sub, rest)) = args.split_first() else {
+        return Ok(Command::Demo { stream_a: None, stream_b: None });
+    };
+    match sub.as_str() {
+        "demo" => {
+            if rest.len() > 2 {
+                return err("demo 最多接受两个作业流文件");
+            }
+            Ok(Command::Demo { stream_a: rest.first().map(PathBuf::from), stream_b: rest.get(1).map(PathBuf::from) })
+        }
+        "run" => parse_run(rest),
+        "compare" => parse_compare(rest),
+        "experiment" => parse_experiment(rest),
+        "generate" => parse_generate(rest),
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        other => err(format!("未知子命令 '{}'", other)),
+    }
+}
+
+// 不带取值的开关选项
+const FLAGS: [&str; 3] = ["-g", "--gantt", "--explain"];
+
+// 把 ["-a", "sjf", "--channels=2", "-g"] 拆成 (选项名, 值) 序列，开关选项的值为空
+fn options(args: &[String]) -> Result<Vec<(String, String)>, CliError> {
+    let mut out = Vec::new();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        if !arg.starts_with('-') {
+            return err(format!("多余的参数 '{}'", arg));
+        }
+        if let Some((name, value)) = arg.split_once('=') {
+            out.push((name.to_string(), value.to_string()));
+            continue;
+        }
+        if FLAGS.contains(&arg.as_str()) {
+            out.push((arg.clone(), String::new()));
+            continue;
+        }
+        match it.next() {
+            Some(value) => out.push((arg.clone(), value.clone())),
+            None => return err(format!("选项 {} 缺少取值", arg)),
+        }
+    }
+    Ok(out)
+}
+
+fn parse_algorithm(s: &str) -> Result<Algorithm, CliError> {
+    Algorithm::parse(s).map_or_else(|| err(format!("未知算法 '{}'", s)), Ok)
+}
+
+fn parse_channels(s: &str) -> Result<usize, CliError> {
+    match s.trim().parse::<usize>() {
+        Ok(m) if m > 0 => Ok(m),
+        _ => err(format!("道数必须是正整数：'{}'", s)),
+    }
+}
+
+fn parse_quantum(s: &str) -> Result<f64, CliError> {
+    match s.trim().parse::<f64>() {
+        Ok(q) if q.is_finite() && q > 0.0 => Ok(q),
+        _ => err(format!("时间片必须是正数：'{}'", s)),
+    }
+}
+
+// 定时事件的周期（--boost、--aging），name 为选项名
+fn parse_interval(name: &str, s: &str) -> Result<f64, CliError> {
+    match s.trim().parse::<f64>() {
+        Ok(p) if p.is_finite() && p > 0.0 => Ok(p),
+        _ => err(format!("{} 的周期必须是正数：'{}'", name, s)),
+    }
+}
+
+fn parse_switch_cost(s: &str) -> Result<f64, CliError> {
+    match s.trim().parse::<f64>() {
+        Ok(c) if c.is_finite() && c >= 0.0 => Ok(c),
+        _ => err(format!("切换开销必须是非负数：'{}'", s)),
+    }
+}
+
+fn parse_threshold(s: &str) -> Result<f64, CliError> {
+    match s.trim().parse::<f64>() {
+        Ok(t) if t.is_finite() && t >= 0.0 => Ok(t),
+        _ => err(format!("饥饿阈值必须是非负数：'{}'", s)),
+    }
+}
+
+fn parse_horizon(s: &str) -> Result<f64, CliError> {
+    match s.trim().parse::<f64>() {
+        Ok(h) if h.is_finite() && h > 0.0 => Ok(h),
+        _ => err(format!("展开区间必须是正数：'{}'", s)),
+    }
+}
+
+fn parse_seed(s: &str) -> Result<u64, CliError> {
+    s.trim().parse::<u64>().map_or_else(|_| err(format!("种子必须是非负整数：'{}'", s)), Ok)
+}
+
+fn parse_clock(s: &str) -> Result<f64, CliError> {
+    clock::parse(s).map_or_else(|| err(format!("时钟时刻应为 H:MM：'{}'", s)), Ok)
+}
+
+// 作业调度只做一次性的挑选，不支持抢占式与时间片算法
+fn parse_job_scheduler(s: &str) -> Result<Algorithm, CliError> {
+    match Algorithm::parse(s) {
+        Some(alg) if !alg.uses_quantum() && !alg.scheduler(&SchedulerConfig::default()).preemptive() => Ok(alg),
+        Some(_) => err(format!("'{}' 不能用作作业调度（应为 fcfs/sjf/hrrn/prio）", s)),
+        None => err(format!("未知算法 '{}'", s)),
+    }
+}
+
+fn parse_capacity(s: &str) -> Result<u64, CliError> {
+    match s.trim().parse::<u64>() {
+        Ok(c) if c > 0 => Ok(c),
+        _ => err(format!("主存容量必须是正整数：'{}'", s)),
+    }
+}
+
+fn parse_levels(s: &str) -> Result<usize, CliError> {
+    match s.trim().parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => err(format!("级数必须是正整数：'{}'", s)),
+    }
+}
+
+// "random" 或 "random:<种子>"，缺省种子为 1
+fn parse_random_seed(s: &str) -> Option<Result<u64, CliError>> {
+    let rest = s.trim().strip_prefix("random")?;
+    match rest.strip_prefix(':') {
+        None if rest.is_empty() => Some(Ok(1)),
+        Some(seed) => Some(parse_seed(seed)),
+        None => None,
+    }
+}
+
+fn parse_tie(s: &str) -> Result<TieBreak, CliError> {
+    if let Some(seed) = parse_random_seed(s) {
+        return Ok(TieBreak::Random(seed?));
+    }
+    match s.trim() {
+        "fifo" => Ok(TieBreak::Fifo),
+        "id" => Ok(TieBreak::LowerId),
+        "arrival" => Ok(TieBreak::EarlierArrival),
+        _ => err(format!("未知取舍规则 '{}'", s)),
+    }
+}
+
+fn parse_channel_tie(s: &str) -> Result<ChannelTie, CliError> {
+    if let Some(seed) = parse_random_seed(s) {
+        return Ok(ChannelTie::Random(seed?));
+    }
+    match s.trim() {
+        "lowest" => Ok(ChannelTie::Lowest),
+        _ => err(format!("未知道分派规则 '{}'", s)),
+    }
+}
+
+// run 与 compare 共用的策略参数
+fn parse_config_option(config: &mut SchedulerConfig, name: &str, value: &str) -> Result<bool, CliError> {
+    match name {
+        "--switch-cost" => config.switch_cost = parse_switch_cost(value)?,
+        "--levels" => config.levels = parse_levels(value)?,
+        "--level-quanta" => config.level_quanta = value.split(',').map(parse_quantum).collect::<Result<_, _>>()?,
+        "--boost" => config.boost = Some(parse_interval(name, value)?),
+        "--aging" => config.aging = Some(parse_interval(name, value)?),
+        "--horizon" => config.horizon = Some(parse_horizon(value)?),
+        "--lottery-seed" => config.lottery_seed = parse_seed(value)?,
+        "--tie" => config.sim.tie = parse_tie(value)?,
+        "--channel-tie" => config.sim.channel_tie = parse_channel_tie(value)?,
+        "--job-scheduler" => config.sim.job_scheduler = Some(parse_job_scheduler(value)?),
+        // 只给 --fit 时主存不限容量：作业到达即调入，只记录所分得的分区
+        "--memory" => {
+            let fit = config.sim.memory.map_or(Fit::First, |mem| mem.fit);
+            config.sim.memory = Some(MemoryModel { capacity: parse_capacity(value)?, fit });
+        }
+        "--fit" => {
+            let fit = Fit::parse(value).map_or_else(|| err(format!("未知分配算法 '{}'（应为 first/best/worst）", value)), Ok)?;
+            config.sim.memory.get_or_insert(MemoryModel { capacity: u64::MAX, fit }).fit = fit;
+        }
+        "--ticks" => {
+            config.sim.time = match value.trim().parse::<u32>() {
+                Ok(n) if n > 0 => TimeMode::Ticks(n),
+                _ => return err(format!("刻度数必须是正整数：'{}'", value)),
+            }
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+// experiment 与 generate 共用的随机作业流参数
+fn parse_workload_option(spec: &mut WorkloadSpec, name: &str, value: &str) -> Result<bool, CliError> {
+    match name {
+        "-n" | "--jobs" => {
+            spec.jobs = match value.trim().parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => return err(format!("作业数必须是正整数：'{}'", value)),
+            }
+        }
+        "--seed" => spec.seed = parse_seed(value)?,
+        "--arrival" => spec.arrival = ArrivalDist::parse(value).map_err(CliError)?,
+        "--service" => spec.service = ServiceDist::parse(value).map_err(CliError)?,
+        "--decimals" => {
+            spec.decimals = match value.trim().parse::<u32>() {
+                Ok(d) if d <= 9 => d,
+                _ => return err(format!("小数位数必须是 0 到 9 的整数：'{}'", value)),
+            }
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn parse_output(s: &str) -> Result<OutputFormat, CliError> {
+    match s {
+        "table" => Ok(OutputFormat::Table),
+        "csv" => Ok(OutputFormat::Csv),
+        "json" => Ok(OutputFormat::Json),
+        "latex" => Ok(OutputFormat::Latex),
+        _ => err(format!("未知输出格式 '{}'", s)),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<Command, CliError> {
+    let mut opts = RunOpts {
+        algorithm: Algorithm::Fcfs,
+        config: SchedulerConfig::default(),
+        channels: 1,
+        input: None,
+        output: OutputFormat::Table,
+        gantt: false,
+        starvation: None,
+        clock: None,
+        svg: None,
+        tex: None,
+        export: None,
+        trace: None,
+        explain: false,
+    };
+    for (name, value) in options(args)? {
+        match name.as_str() {
+            "-a" | "--algorithm" => opts.algorithm = parse_algorithm(&value)?,
+            "-q" | "--quantum" => opts.config.quantum = parse_quantum(&value)?,
+            "-m" | "--channels" => opts.channels = parse_channels(&value)?,
+            "-i" | "--input" => opts.input = Some(PathBuf::from(value)),
+            "-f" | "--format" => opts.output = parse_output(&value)?,
+            "-g" | "--gantt" => opts.gantt = true,
+            "--starvation" => opts.starvation = Some(parse_threshold(&value)?),
+            "--clock" => opts.clock = Some(parse_clock(&value)?),
+            "--svg" => opts.svg = Some(PathBuf::from(value)),
+            "--tex" => opts.tex = Some(PathBuf::from(value)),
+            "-e" | "--export" => opts.export = Some(PathBuf::from(value)),
+            "--trace" => opts.trace = Some(PathBuf::from(value)),
+            "--explain" => opts.explain = true,
+            _ if parse_config_option(&mut opts.config, &name, &value)? => {}
+            _ => return err(format!("run 不支持选项 {}", name)),
+        }
+    }
+    Ok(Command::Run(opts))
+}
+
+fn parse_compare(args: &[String]) -> Result<Command, CliError> {
+    let config = SchedulerConfig::default();
+    let quanta = vec![config.quantum];
+    let mut opts = CompareOpts {
+        algorithms: Algorithm::ALL.to_vec(),
+        config,
+        quanta,
+        channels: vec![1],
+        input: None,
+        output: OutputFormat::Table,
+        gantt: false,
+        starvation: None,
+        clock: None,
+        svg_dir: None,
+        tex_dir: None,
+        export: None,
+    };
+    for (name, value) in options(args)? {
+        match name.as_str() {
+            "-a" | "--algorithm" => opts.algorithms = value.split(',').map(parse_algorithm).collect::<Result<_, _>>()?,
+            "-q" | "--quantum" => opts.quanta = value.split(',').map(parse_quantum).collect::<Result<_, _>>()?,
+            "-m" | "--channels" => opts.channels = value.split(',').map(parse_channels).collect::<Result<_, _>>()?,
+            "-i" | "--input" => opts.input = Some(PathBuf::from(value)),
+            "-f" | "--format" => opts.output = parse_output(&value)?,
+            "-g" | "--gantt" => opts.gantt = true,
+            "--starvation" => opts.starvation = Some(parse_threshold(&value)?),
+            "--clock" => opts.clock = Some(parse_clock(&value)?),
+            "--svg" => opts.svg_dir = Some(PathBuf::from(value)),
+            "--tex" => opts.tex_dir = Some(PathBuf::from(value)),
+            "-e" | "--export" => opts.export = Some(PathBuf::from(value)),
+            _ if parse_config_option(&mut opts.config, &name, &value)? => {}
+            _ => return err(format!("compare 不支持选项 {}", name)),
+        }
+    }
+    Ok(Command::Compare(opts))
+}
+
+fn parse_experiment(args: &[String]) -> Result<Command, CliError> {
+    let config = SchedulerConfig::default();
+    let quanta = vec![config.quantum];
+    let mut opts = ExperimentOpts {
+        algorithms: Algorithm::ALL.to_vec(),
+        config,
+        quanta,
+        channels: vec![1],
+        workload: WorkloadSpec::default(),
+        workloads: 30,
+        output: OutputFormat::Table,
+    };
+    for (name, value) in options(args)? {
+        match name.as_str() {
+            "-a" | "--algorithm" => opts.algorithms = value.split(',').map(parse_algorithm).collect::<Result<_, _>>()?,
+            "-q" | "--quantum" => opts.quanta = value.split(',').map(parse_quantum).collect::<Result<_, _>>()?,
+            "-m" | "--channels" => opts.channels = value.split(',').map(parse_channels).collect::<Result<_, _>>()?,
+            "-w" | "--workloads" => {
+                opts.workloads = match value.trim().parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return err(format!("作业流个数必须是正整数：'{}'", value)),
+                }
+            }
+            "-f" | "--format" => opts.output = parse_output(&value)?,
+            _ if parse_config_option(&mut opts.config, &name, &value)? => {}
+            _ if parse_workload_option(&mut opts.workload, &name, &value)? => {}
+            _ => return err(format!("experiment 不支持选项 {}", name)),
+        }
+    }
+    Ok(Command::Experiment(opts))
+}
+
+fn parse_generate(args: &[String]) -> Result<Command, CliError> {
+    let mut sample = None;
+    let mut spec = WorkloadSpec::default();
+    let mut random = false;
+    let mut format = None;
+    let mut output: Option<PathBuf> = None;
+    for (name, value) in options(args)? {
+        match name.as_str() {
+            "-s" | "--sample" => {
+                sample = match value.to_ascii_lowercase().as_str() {
+                    "a" => Some(Sample::A),
+                    "b" => Some(Sample::B),
+                    _ => return err(format!("未知样例 '{}'", value)),
+                }
+            }
+            "-f" | "--format" => format = Some(Format::parse(&value).map_or_else(|| err(format!("未知文件格式 '{}'", value)), Ok)?),
+            "-o" | "--output" => output = Some(PathBuf::from(value)),
+            _ if parse_workload_option(&mut spec, &name, &value)? => random = true,
+            _ => return err(format!("generate 不支持选项 {}", name)),
+        }
+    }
+    let workload = match (sample, random) {
+        (Some(_), true) => return err("--sample 不能与随机生成选项同时使用"),
+        (_, true) => Workload::Random(spec),
+        (sample, false) => Workload::Sample(sample.unwrap_or(Sample::A)),
+    };
+    let format = format.or_else(|| output.as_deref().and_then(Format::from_path)).unwrap_or(Format::Csv);
+    Ok(Command::Generate(GenerateOpts { workload, format, output }))
+}
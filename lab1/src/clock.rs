@@ -0,0 +1,58 @@
+// 时钟时刻（如 8:00、8:50、13:05:30）与当天零点起的分钟数之间的换算
+
+// H:MM 或 H:MM:SS；分、秒必须是两位且小于 60
+pub fn parse(s: &str) -> Option<f64> {
+    let parts: Vec<&str> = s.trim().split(':').collect();
+    if !(2..=3).contains(&parts.len()) || parts[1..].iter().any(|p| p.len() != 2) {
+        return None;
+    }
+    let num = |p: &str| p.parse::<u32>().ok();
+    let (h, m) = (num(parts[0])?, num(parts[1])?);
+    let sec = match parts.get(2) {
+        Some(p) => num(p)?,
+        None => 0,
+    };
+    if m >= 60 || sec >= 60 {
+        return None;
+    }
+    Some(h as f64 * 60.0 + m as f64 + sec as f64 / 60.0)
+}
+
+// 分钟数 -> H:MM，不足一分钟的部分四舍五入到秒并写成 H:MM:SS；超过 24 小时不回绕
+pub fn format(minutes: f64) -> String {
+    let total = (minutes * 60.0).round() as i64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    if s == 0 { format!("{}:{:02}", h, m) } else { format!("{}:{:02}:{:02}", h, m, s) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rejects_out_of_range_fields() {
+        assert_eq!(parse("8:50"), Some(530.0));
+        assert_eq!(parse(" 08:05:30 "), Some(485.5));
+        for bad in ["8:60", "8:5", "8:005", "8:00:60", "8:00:5", "8", "8:00:00:00", "-1:00", "8:-1", "a:00", ""] {
+            assert_eq!(parse(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn times_past_midnight_do_not_wrap() {
+        assert_eq!(parse("24:00"), Some(1440.0));
+        assert_eq!(parse("25:30"), Some(1530.0));
+        assert_eq!(format(1530.0), "25:30");
+        assert_eq!(format(23.0 * 60.0 + 59.9999), "24:00");
+    }
+
+    #[test]
+    fn format_round_trips() {
+        for s in ["0:00", "8:50", "13:05:30", "23:59:59", "30:00:01"] {
+            assert_eq!(format(parse(s).unwrap()), s);
+        }
+        // 不足一秒的部分四舍五入到秒
+        assert_eq!(format(530.0 + 0.4 / 60.0), "8:50");
+        assert_eq!(format(530.0 + 29.6 / 60.0), "8:50:30");
+    }
+}
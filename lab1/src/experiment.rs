@@ -0,0 +1,184 @@
+// 批量实验：每种算法 × 每个道数 × N 个随机作业流，统计平均（带权）周转时间的
+// 均值、标准差与 95% 置信区间
+
+use crate::generator::{self, WorkloadSpec};
+use crate::job::JobStream;
+use crate::json::Value;
+use crate::result::{csv_field, Metrics};
+use crate::scheduler::{simulate, ScheduleError, Scheduler, SimOptions};
+
+// 一组样本的统计量；样本不足 2 个时没有标准差与置信区间
+#[derive(Clone, Copy, Debug)]
+pub struct Summary {
+    pub n: usize,
+    pub mean: f64,
+    pub std_dev: Option<f64>, // 样本标准差（除以 n − 1）
+    pub ci95: Option<f64>,    // 95% 置信区间的半宽（t 分布）
+}
+
+impl Summary {
+    pub fn of(xs: &[f64]) -> Option<Summary> {
+        let n = xs.len();
+        if n == 0 {
+            return None;
+        }
+        let mean = xs.iter().sum::<f64>() / n as f64;
+        let std_dev = (n > 1).then(|| (xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt());
+        let ci95 = std_dev.map(|s| t95(n - 1) * s / (n as f64).sqrt());
+        Some(Summary { n, mean, std_dev, ci95 })
+    }
+
+    fn to_json_value(self) -> Value {
+        Value::Obj(vec![
+            ("n".into(), self.n.into()),
+            ("mean".into(), self.mean.into()),
+            ("std_dev".into(), self.std_dev.into()),
+            ("ci95".into(), self.ci95.into()),
+        ])
+    }
+}
+
+// 双侧 95% 的 t 分位数；自由度 30 以上按表线性插值，超过 120 取正态分位数
+fn t95(df: usize) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160, 2.145, 2.131,
+        2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052, 2.048, 2.045, 2.042,
+    ];
+    let lerp = |lo: usize, hi: usize, a: f64, b: f64| a + (b - a) * (df - lo) as f64 / (hi - lo) as f64;
+    match df {
+        0 => f64::NAN,
+        1..=30 => TABLE[df - 1],
+        31..=40 => lerp(30, 40, 2.042, 2.021),
+        41..=60 => lerp(40, 60, 2.021, 2.000),
+        61..=120 => lerp(60, 120, 2.000, 1.980),
+        _ => 1.960,
+    }
+}
+
+// 一种配置（算法 × 道数）在全部作业流上的统计
+#[derive(Clone, Debug)]
+pub struct ExperimentRow {
+    pub algorithm: String,
+    pub channels: usize,
+    pub turnaround: Option<Summary>,
+    pub weighted_turnaround: Option<Summary>,
+    pub wins: usize, // 同一道数下，平均周转时间最小的作业流个数（并列都计）
+}
+
+#[derive(Clone, Debug)]
+pub struct Experiment {
+    pub spec: WorkloadSpec,
+    pub workloads: usize,
+    pub rows: Vec<ExperimentRow>,
+}
+
+// 第 k 个作业流使用种子 spec.seed + k
+fn workloads(spec: &WorkloadSpec, count: usize) -> Result<Vec<JobStream>, ScheduleError> {
+    (0..count)
+        .map(|k| JobStream::new(generator::generate(&WorkloadSpec { seed: spec.seed.wrapping_add(k as u64), ..spec.clone() })))
+        .collect()
+}
+
+pub fn run(
+    policies: &[Box<dyn Scheduler>],
+    channels: &[usize],
+    spec: &WorkloadSpec,
+    count: usize,
+    sim: SimOptions,
+) -> Result<Experiment, ScheduleError> {
+    let streams = workloads(spec, count)?;
+    let mut rows = Vec::new();
+    for &m in channels {
+        // metrics[p][k]：第 p 个算法在第 k 个作业流上的汇总指标
+        let mut metrics: Vec<Vec<Metrics>> = Vec::with_capacity(policies.len());
+        for policy in policies {
+            let per = streams.iter().map(|jobs| Ok(Metrics::from_jobs(&simulate(jobs, m, policy.as_ref(), sim)?, sim.cpus(m))));
+            metrics.push(per.collect::<Result<_, ScheduleError>>()?);
+        }
+        let mut wins = vec![0; policies.len()];
+        for k in 0..streams.len() {
+            let best = metrics.iter().filter_map(|per| per[k].avg_turnaround).fold(f64::INFINITY, f64::min);
+            for (p, per) in metrics.iter().enumerate() {
+                if per[k].avg_turnaround.is_some_and(|t| t <= best + 1e-9) {
+                    wins[p] += 1;
+                }
+            }
+        }
+        for (p, policy) in policies.iter().enumerate() {
+            let turns: Vec<f64> = metrics[p].iter().filter_map(|x| x.avg_turnaround).collect();
+            let wturns: Vec<f64> = metrics[p].iter().filter_map(|x| x.avg_weighted_turnaround).collect();
+            rows.push(ExperimentRow {
+                algorithm: sim.label(policy.as_ref()),
+                channels: m,
+                turnaround: Summary::of(&turns),
+                weighted_turnaround: Summary::of(&wturns),
+                wins: wins[p],
+            });
+        }
+    }
+    Ok(Experiment { spec: spec.clone(), workloads: count, rows })
+}
+
+impl Experiment {
+    // 作业流设置的一行说明
+    pub fn describe(&self) -> String {
+        format!(
+            "{} 个作业流 × {} 个作业，到达间隔 {}，运行时间 {}，种子 {}..={}",
+            self.workloads,
+            self.spec.jobs,
+            self.spec.arrival,
+            self.spec.service,
+            self.spec.seed,
+            self.spec.seed.wrapping_add(self.workloads.saturating_sub(1) as u64)
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .map(|r| {
+                Value::Obj(vec![
+                    ("algorithm".into(), r.algorithm.as_str().into()),
+                    ("channels".into(), r.channels.into()),
+                    ("avg_turnaround".into(), r.turnaround.map_or(Value::Null, Summary::to_json_value)),
+                    ("avg_weighted_turnaround".into(), r.weighted_turnaround.map_or(Value::Null, Summary::to_json_value)),
+                    ("wins".into(), r.wins.into()),
+                ])
+            })
+            .collect();
+        let spec = Value::Obj(vec![
+            ("workloads".into(), self.workloads.into()),
+            ("jobs".into(), self.spec.jobs.into()),
+            ("seed".into(), Value::Num(self.spec.seed as f64)),
+            ("arrival".into(), self.spec.arrival.to_string().as_str().into()),
+            ("service".into(), self.spec.service.to_string().as_str().into()),
+        ]);
+        Value::Obj(vec![("workload".into(), spec), ("results".into(), Value::Arr(rows))]).to_pretty()
+    }
+
+    // 每个配置一行；缺失的统计量为空
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("algorithm,m,workloads,turn_mean,turn_std,turn_ci95,wturn_mean,wturn_std,wturn_ci95,wins\n");
+        let cells = |s: Option<Summary>| match s {
+            Some(s) => format!("{},{},{}", s.mean, opt(s.std_dev), opt(s.ci95)),
+            None => ",,".to_string(),
+        };
+        for r in &self.rows {
+            out.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                csv_field(&r.algorithm),
+                r.channels,
+                self.workloads,
+                cells(r.turnaround),
+                cells(r.weighted_turnaround),
+                r.wins
+            ));
+        }
+        out
+    }
+}
+
+fn opt(v: Option<f64>) -> String {
+    v.map_or(String::new(), |v| v.to_string())
+}
@@ -0,0 +1,149 @@
+// 讲解模式：把事件日志（trace.rs）写成教材习题解答式的文字，逐个时刻说明发生了什么，
+// 每次分派列出就绪队列中各候选的等待时间与挑选依据（HRRN 的响应比、SJF 的运行时间等），
+// 并说明选中哪个作业、为什么——用于核对报告中表格的先后顺序。两级调度时作业调度的每次调入也同样说明；
+// 彩票调度没有挑选依据，候选表列出彩票数，并给出中签作业的彩票占比
+
+use crate::clock;
+use crate::scheduler::{Scheduler, TieBreak};
+use crate::trace::{Candidate, EventKind, Trace, TraceEvent};
+
+// 调度时做出选择的一方：进程调度（分派）或作业调度（调入）
+struct Chooser<'a> {
+    policy: &'a dyn Scheduler,
+    rule: String, // 取舍规则的说明
+}
+
+impl Chooser<'_> {
+    fn header(&self, who: &str) -> String {
+        match self.policy.criterion() {
+            Some((name, higher)) => {
+                let order = if higher { "大" } else { "小" };
+                format!("{}的挑选依据：{}，取{}者{}；相等时{}优先\n", who, name, order, formula(name), self.rule)
+            }
+            None if self.policy.proportional() => format!("{}的挑选依据：按彩票数加权抽签，作业中签的概率为其彩票数占就绪作业彩票总数的比例\n", who),
+            None => format!("{}的挑选依据：就绪队列先进先出\n", who),
+        }
+    }
+}
+
+// policy 为进程调度策略，job_policy 为两级调度时的作业调度策略（None 时作业调度沿用 policy），
+// tie 为取舍规则，clock 给出时按时钟时刻显示
+pub fn render(trace: &Trace, policy: &dyn Scheduler, job_policy: Option<&dyn Scheduler>, tie: TieBreak, clock: Option<f64>) -> String {
+    let at = |t: f64| match clock {
+        Some(c) => clock::format(c + t),
+        None => format!("{:.2}", t),
+    };
+    let process = Chooser { policy, rule: tie_rule(policy, tie) };
+    let job_policy = job_policy.unwrap_or(policy);
+    let job = Chooser { policy: job_policy, rule: tie_rule(job_policy, tie) };
+    let mut out = process.header("进程调度");
+    if trace.events.iter().any(|e| e.kind == EventKind::Admit) {
+        out.push_str(&job.header("作业调度"));
+    }
+
+    // 同一时刻的事件归为一段
+    let events = &trace.events;
+    let mut i = 0;
+    while i < events.len() {
+        let time = events[i].time;
+        let end = i + events[i..].iter().take_while(|e| e.time == time).count();
+        out.push_str(&format!("\n时刻 {}：\n", at(time)));
+        let arrived: Vec<String> =
+            events[i..end].iter().filter(|e| e.kind == EventKind::Arrive).filter_map(|e| e.job).map(|id| id.to_string()).collect();
+        if !arrived.is_empty() {
+            out.push_str(&format!("  作业 {} 到达\n", arrived.join("、")));
+        }
+        for e in &events[i..end] {
+            out.push_str(&describe(e, &process, &job, &at));
+        }
+        out.push_str(&format!("  就绪队列：{}\n", queue(&events[end - 1].ready)));
+        i = end;
+    }
+    out
+}
+
+// 一条事件的说明；到达已在段首合并列出，进入就绪队列与道转忙由前后的事件说明
+fn describe(e: &TraceEvent, process: &Chooser, admission: &Chooser, at: &dyn Fn(f64) -> String) -> String {
+    let job = e.job.unwrap_or(0);
+    let channel = e.channel.unwrap_or(0);
+    match e.kind {
+        EventKind::Arrive | EventKind::Ready | EventKind::Busy => String::new(),
+        EventKind::Admit => choice(e, admission, at, &format!("作业调度调入作业 {}", job)),
+        EventKind::Expire => format!("  作业 {} 在道 {} 上用完时间片，回到就绪队列末尾\n", job, channel),
+        EventKind::Preempt => format!("  作业 {} 在道 {} 上被抢占，回到就绪队列\n", job, channel),
+        EventKind::Complete => format!("  作业 {} 在道 {} 上完成\n", job, channel),
+        EventKind::Period => format!("  定时事件（{}）\n", process.policy.label()),
+        EventKind::Idle => format!("  道 {} 空闲\n", channel),
+        EventKind::Dispatch => choice(e, process, at, &format!("道 {} 选中作业 {}", channel, job)),
+    }
+}
+
+// 一次分派或调入：候选表与选择理由
+fn choice(e: &TraceEvent, chooser: &Chooser, at: &dyn Fn(f64) -> String, verdict: &str) -> String {
+    // 到达时间、运行时间已在候选表中，作为依据时不再重复一列
+    let extra = match chooser.policy.criterion() {
+        Some((name, _)) => Some(name).filter(|name| !matches!(*name, "到达时间" | "运行时间")),
+        None => chooser.policy.proportional().then_some("彩票数"),
+    };
+    let mut out = String::from("  候选：作业\t到达时间\t运行时间\t已等待");
+    if let Some(name) = extra {
+        out.push_str(&format!("\t{}", name));
+    }
+    out.push('\n');
+    for c in &e.candidates {
+        out.push_str(&format!("        {}\t{}\t{:.2}\t{:.2}", c.job, at(c.arrival), c.service, c.wait));
+        if extra.is_some() {
+            out.push_str(&format!("\t{:.2}", c.value));
+        }
+        out.push('\n');
+    }
+    out.push_str(&format!("  → {}：{}\n", verdict, reason(&e.candidates, e.job.unwrap_or(0), chooser)));
+    out
+}
+
+fn reason(candidates: &[Candidate], job: usize, chooser: &Chooser) -> String {
+    if candidates.len() == 1 {
+        return "唯一的候选".to_string();
+    }
+    let winner = candidates.iter().find(|c| c.job == job);
+    if let (None, Some(winner), true) = (chooser.policy.criterion(), winner, chooser.policy.proportional()) {
+        let total: f64 = candidates.iter().map(|c| c.value).sum();
+        return format!("抽签选中（彩票 {} / {}，中签概率 {:.1}%）", winner.value, total, winner.value / total * 100.0);
+    }
+    let (Some((name, higher)), Some(winner)) = (chooser.policy.criterion(), winner) else {
+        return "就绪队列队首".to_string();
+    };
+    let mut text = format!("{}{}（{:.2}）", name, if higher { "最大" } else { "最小" }, winner.value);
+    // 与引擎（TieBreaker）一样按取值精确相等判断，浮点误差造成的差别不算相同
+    let tied: Vec<String> =
+        candidates.iter().filter(|c| c.job != job && c.value == winner.value).map(|c| c.job.to_string()).collect();
+    if !tied.is_empty() {
+        text.push_str(&format!("，与作业 {} 相同，{}优先", tied.join("、"), chooser.rule));
+    }
+    text
+}
+
+fn formula(name: &str) -> &'static str {
+    match name {
+        "响应比" => "（响应比 = (已等待 + 运行时间) / 运行时间）",
+        "剩余时间" => "（新进入就绪队列的作业更短时抢占运行中的作业）",
+        _ => "",
+    }
+}
+
+fn tie_rule(policy: &dyn Scheduler, tie: TieBreak) -> String {
+    match tie {
+        _ if policy.fifo_ties() => "先进入就绪队列者".to_string(),
+        TieBreak::Fifo => "先进入就绪队列者".to_string(),
+        TieBreak::LowerId => "id 小者".to_string(),
+        TieBreak::EarlierArrival => "到达早者".to_string(),
+        TieBreak::Random(seed) => format!("随机选取者（种子 {}）", seed),
+    }
+}
+
+fn queue(ids: &[usize]) -> String {
+    if ids.is_empty() {
+        return "空".to_string();
+    }
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,200 @@
+// 甘特图：按道列出各作业的执行区间（含空闲间隙），输出 ASCII 或 SVG
+
+use crate::job::Job;
+
+// 道上的一个区间：job 为 None 表示空闲
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Slot {
+    pub job: Option<usize>,
+    pub start: f64,
+    pub end: f64,
+}
+
+// 各道的时间线（从 0 到全部作业完成），空闲间隙显式列出
+pub fn timelines(jobs: &[Job], m: usize) -> Vec<Vec<Slot>> {
+    let makespan = makespan(jobs);
+    let mut lines: Vec<Vec<Slot>> = vec![Vec::new(); m];
+    for j in jobs {
+        for s in &j.segments {
+            lines[s.channel].push(Slot { job: Some(j.id), start: s.start, end: s.end });
+        }
+    }
+    for line in lines.iter_mut() {
+        line.sort_by(|a, b| a.start.total_cmp(&b.start));
+        let mut filled = Vec::with_capacity(line.len() * 2 + 1);
+        let mut t = 0.0;
+        for slot in line.iter() {
+            if slot.start > t {
+                filled.push(Slot { job: None, start: t, end: slot.start });
+            }
+            filled.push(*slot);
+            t = slot.end;
+        }
+        if makespan > t {
+            filled.push(Slot { job: None, start: t, end: makespan });
+        }
+        *line = filled;
+    }
+    lines
+}
+
+fn makespan(jobs: &[Job]) -> f64 {
+    jobs.iter().filter_map(|j| j.end).fold(0.0, f64::max)
+}
+
+fn fmt_time(t: f64) -> String {
+    let s = format!("{:.2}", t);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+// ASCII 甘特图：width 为时间轴所占字符数；'|' 为区间边界，'.' 为空闲
+pub fn render_ascii(jobs: &[Job], m: usize, width: usize) -> String {
+    let lines = timelines(jobs, m);
+    let makespan = makespan(jobs);
+    if makespan <= 0.0 {
+        return String::new();
+    }
+    let scale = width as f64 / makespan;
+    let col = |t: f64| ((t * scale).round() as usize).min(width);
+    let prefix = format!("道{} ", m.saturating_sub(1)).chars().count();
+
+    let mut out = String::new();
+    let mut bounds: Vec<f64> = vec![0.0, makespan];
+    for (k, line) in lines.iter().enumerate() {
+        let mut row = vec![' '; width + 1];
+        for slot in line {
+            let (a, b) = (col(slot.start), col(slot.end));
+            bounds.push(slot.start);
+            bounds.push(slot.end);
+            let fill = if slot.job.is_some() { ' ' } else { '.' };
+            for c in row.iter_mut().take(b).skip(a + 1) {
+                *c = fill;
+            }
+            if let Some(id) = slot.job {
+                let label: Vec<char> = format!("J{}", id).chars().collect();
+                if b > a + label.len() {
+                    for (i, ch) in label.into_iter().enumerate() {
+                        row[a + 1 + i] = ch;
+                    }
+                } else {
+                    for c in row.iter_mut().take(b).skip(a + 1) {
+                        *c = '#';
+                    }
+                }
+            }
+            row[a] = '|';
+            row[b] = '|';
+        }
+        let head = format!("道{}", k);
+        out.push_str(&format!("{:<width$}{}\n", head, row.iter().collect::<String>(), width = prefix));
+    }
+
+    // 时间轴：在区间边界处标注时刻，放不下的省略
+    bounds.sort_by(|a, b| a.total_cmp(b));
+    bounds.dedup();
+    let mut axis = vec![' '; width + 8];
+    let mut next_free = 0;
+    for t in bounds {
+        let c = col(t);
+        let label: Vec<char> = fmt_time(t).chars().collect();
+        if c < next_free || c + label.len() > axis.len() {
+            continue;
+        }
+        for (i, ch) in label.iter().enumerate() {
+            axis[c + i] = *ch;
+        }
+        next_free = c + label.len() + 1;
+    }
+    out.push_str(&format!("{:<width$}{}\n", "", axis.iter().collect::<String>().trim_end(), width = prefix));
+    out
+}
+
+// 作业颜色：按 id 在色环上均匀取色
+fn color(id: usize) -> String {
+    format!("hsl({}, 65%, 62%)", (id * 137) % 360)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// SVG 甘特图，供报告插图使用
+pub fn render_svg(jobs: &[Job], m: usize, title: &str) -> String {
+    let lines = timelines(jobs, m);
+    let makespan = makespan(jobs).max(f64::MIN_POSITIVE);
+    let (left, top, row_h, plot_w) = (60.0, 40.0, 36.0, 720.0);
+    let scale = plot_w / makespan;
+    let width = left + plot_w + 30.0;
+    let height = top + row_h * m as f64 + 40.0;
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        w = width,
+        h = height
+    ));
+    out.push_str(&format!("  <text x=\"{}\" y=\"22\" font-size=\"15\">{}</text>\n", left, escape(title)));
+
+    let mut bounds: Vec<f64> = vec![0.0, makespan];
+    for (k, line) in lines.iter().enumerate() {
+        let y = top + row_h * k as f64;
+        out.push_str(&format!("  <text x=\"{}\" y=\"{:.1}\" text-anchor=\"end\">道{}</text>\n", left - 8.0, y + row_h / 2.0 + 4.0, k));
+        for slot in line {
+            bounds.push(slot.start);
+            bounds.push(slot.end);
+            let x = left + slot.start * scale;
+            let w = (slot.end - slot.start) * scale;
+            match slot.job {
+                Some(id) => {
+                    out.push_str(&format!(
+                        "  <rect x=\"{:.2}\" y=\"{:.1}\" width=\"{:.2}\" height=\"{:.1}\" fill=\"{}\" stroke=\"#333\"/>\n",
+                        x,
+                        y + 4.0,
+                        w,
+                        row_h - 8.0,
+                        color(id)
+                    ));
+                    out.push_str(&format!(
+                        "  <text x=\"{:.2}\" y=\"{:.1}\" text-anchor=\"middle\">J{}</text>\n",
+                        x + w / 2.0,
+                        y + row_h / 2.0 + 4.0,
+                        id
+                    ));
+                }
+                None => {
+                    out.push_str(&format!(
+                        "  <rect x=\"{:.2}\" y=\"{:.1}\" width=\"{:.2}\" height=\"{:.1}\" fill=\"#eee\" stroke=\"#bbb\" stroke-dasharray=\"3,2\"/>\n",
+                        x,
+                        y + 4.0,
+                        w,
+                        row_h - 8.0
+                    ));
+                }
+            }
+        }
+    }
+
+    // 时间轴与区间边界刻度
+    let axis_y = top + row_h * m as f64 + 4.0;
+    out.push_str(&format!(
+        "  <line x1=\"{}\" y1=\"{:.1}\" x2=\"{}\" y2=\"{:.1}\" stroke=\"#333\"/>\n",
+        left,
+        axis_y,
+        left + plot_w,
+        axis_y
+    ));
+    bounds.sort_by(|a, b| a.total_cmp(b));
+    bounds.dedup();
+    let mut last_label = f64::NEG_INFINITY;
+    for t in bounds {
+        let x = left + t * scale;
+        out.push_str(&format!("  <line x1=\"{:.2}\" y1=\"{:.1}\" x2=\"{:.2}\" y2=\"{:.1}\" stroke=\"#333\"/>\n", x, axis_y, x, axis_y + 5.0));
+        // 刻度过密时只画刻度线，不重叠标注
+        if x - last_label >= 24.0 {
+            out.push_str(&format!("  <text x=\"{:.2}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n", x, axis_y + 18.0, fmt_time(t)));
+            last_label = x;
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
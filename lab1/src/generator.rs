@@ -0,0 +1,248 @@
+// 随机作业流生成：到达间隔与运行时间各自服从可选的分布，固定种子可复现
+
+use std::fmt;
+
+use crate::job::Job;
+use crate::rng::Rng;
+
+// 到达过程（相邻作业的到达间隔）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArrivalDist {
+    // 泊松到达：间隔服从均值为 mean 的指数分布
+    Exponential { mean: f64 },
+    Uniform { min: f64, max: f64 },
+    // 突发到达：突发之间的间隔均值为 gap，每次突发平均 size 个作业，突发内间隔均值为 within
+    Bursty { size: f64, gap: f64, within: f64 },
+}
+
+// 运行时间分布
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ServiceDist {
+    Exponential { mean: f64 },
+    Uniform { min: f64, max: f64 },
+    // 双峰：以概率 p_short 取 short 附近的短作业，否则取 long 附近的长作业（±25% 均匀扰动）
+    Bimodal { short: f64, long: f64, p_short: f64 },
+    // 重尾：Pareto 分布，最小值 scale，形状参数 shape（越小尾部越重）
+    Pareto { scale: f64, shape: f64 },
+}
+
+// 解析 "名称:参数1,参数2,..."，参数个数不符或非正数时返回错误说明
+fn parse_params(spec: &str) -> Result<(String, Vec<f64>), String> {
+    let (name, params) = spec.split_once(':').unwrap_or((spec, ""));
+    let values = params
+        .split(',')
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| match p.trim().parse::<f64>() {
+            Ok(v) if v.is_finite() && v >= 0.0 => Ok(v),
+            _ => Err(format!("分布参数必须是非负数：'{}'", p)),
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+    Ok((name.trim().to_ascii_lowercase(), values))
+}
+
+fn arity(name: &str, values: &[f64], n: usize) -> Result<(), String> {
+    if values.len() == n {
+        Ok(())
+    } else {
+        Err(format!("分布 {} 需要 {} 个参数", name, n))
+    }
+}
+
+// uniform 的两个参数：最小值不能大于最大值
+fn range(name: &str, values: &[f64]) -> Result<(f64, f64), String> {
+    arity(name, values, 2)?;
+    if values[0] > values[1] {
+        return Err(format!("{} 的最小值 {} 大于最大值 {}", name, values[0], values[1]));
+    }
+    Ok((values[0], values[1]))
+}
+
+impl ArrivalDist {
+    // exp:<均值> | uniform:<最小>,<最大> | bursty:<突发规模>,<突发间隔>,<突发内间隔>
+    pub fn parse(spec: &str) -> Result<ArrivalDist, String> {
+        let (name, v) = parse_params(spec)?;
+        match name.as_str() {
+            "exp" | "poisson" => arity(&name, &v, 1).map(|_| ArrivalDist::Exponential { mean: v[0] }),
+            "uniform" => range(&name, &v).map(|(min, max)| ArrivalDist::Uniform { min, max }),
+            "bursty" => arity(&name, &v, 3).map(|_| ArrivalDist::Bursty { size: v[0].max(1.0), gap: v[1], within: v[2] }),
+            _ => Err(format!("未知到达分布 '{}'", name)),
+        }
+    }
+}
+
+impl ServiceDist {
+    // exp:<均值> | uniform:<最小>,<最大> | bimodal:<短>,<长>,<短作业比例> | pareto:<最小值>,<形状>
+    pub fn parse(spec: &str) -> Result<ServiceDist, String> {
+        let (name, v) = parse_params(spec)?;
+        match name.as_str() {
+            "exp" => arity(&name, &v, 1).map(|_| ServiceDist::Exponential { mean: v[0] }),
+            "uniform" => range(&name, &v).map(|(min, max)| ServiceDist::Uniform { min, max }),
+            "bimodal" => arity(&name, &v, 3).map(|_| ServiceDist::Bimodal { short: v[0], long: v[1], p_short: v[2].min(1.0) }),
+            "pareto" => match arity(&name, &v, 2) {
+                Ok(()) if v[1] > 0.0 => Ok(ServiceDist::Pareto { scale: v[0], shape: v[1] }),
+                Ok(()) => Err("pareto 的形状参数必须为正".to_string()),
+                Err(e) => Err(e),
+            },
+            _ => Err(format!("未知运行时间分布 '{}'", name)),
+        }
+    }
+
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        match *self {
+            ServiceDist::Exponential { mean } => rng.exponential(mean),
+            ServiceDist::Uniform { min, max } => rng.uniform(min, max),
+            ServiceDist::Bimodal { short, long, p_short } => {
+                let center = if rng.next_f64() < p_short { short } else { long };
+                rng.uniform(center * 0.75, center * 1.25)
+            }
+            ServiceDist::Pareto { scale, shape } => scale / (1.0 - rng.next_f64()).powf(1.0 / shape),
+        }
+    }
+}
+
+impl fmt::Display for ArrivalDist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrivalDist::Exponential { mean } => write!(f, "exp:{}", mean),
+            ArrivalDist::Uniform { min, max } => write!(f, "uniform:{},{}", min, max),
+            ArrivalDist::Bursty { size, gap, within } => write!(f, "bursty:{},{},{}", size, gap, within),
+        }
+    }
+}
+
+impl fmt::Display for ServiceDist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceDist::Exponential { mean } => write!(f, "exp:{}", mean),
+            ServiceDist::Uniform { min, max } => write!(f, "uniform:{},{}", min, max),
+            ServiceDist::Bimodal { short, long, p_short } => write!(f, "bimodal:{},{},{}", short, long, p_short),
+            ServiceDist::Pareto { scale, shape } => write!(f, "pareto:{},{}", scale, shape),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WorkloadSpec {
+    pub jobs: usize,
+    pub seed: u64,
+    pub arrival: ArrivalDist,
+    pub service: ServiceDist,
+    pub decimals: u32, // 时间保留的小数位数
+}
+
+impl Default for WorkloadSpec {
+    fn default() -> Self {
+        Self {
+            jobs: 20,
+            seed: 1,
+            arrival: ArrivalDist::Exponential { mean: 2.0 },
+            service: ServiceDist::Exponential { mean: 4.0 },
+            decimals: 2,
+        }
+    }
+}
+
+// 生成作业流：第一个作业在 0 时刻到达，id 从 1 开始
+pub fn generate(spec: &WorkloadSpec) -> Vec<Job> {
+    let mut rng = Rng::new(spec.seed);
+    let scale = 10f64.powi(spec.decimals as i32);
+    let round = |x: f64| (x * scale).round() / scale;
+    let min_service = 1.0 / scale;
+
+    let mut jobs = Vec::with_capacity(spec.jobs);
+    let mut t = 0.0;
+    let mut burst_left = 0usize; // 当前突发中还剩几个作业
+    for i in 0..spec.jobs {
+        if i > 0 {
+            t += match spec.arrival {
+                ArrivalDist::Exponential { mean } => rng.exponential(mean),
+                ArrivalDist::Uniform { min, max } => rng.uniform(min, max),
+                ArrivalDist::Bursty { size, gap, within } => {
+                    if burst_left == 0 {
+                        // 突发规模服从均值为 size 的几何分布
+                        burst_left = 1;
+                        while rng.next_f64() > 1.0 / size {
+                            burst_left += 1;
+                        }
+                        rng.exponential(gap)
+                    } else {
+                        rng.exponential(within)
+                    }
+                }
+            };
+            burst_left = burst_left.saturating_sub(1);
+        }
+        let service = round(spec.service.sample(&mut rng)).max(min_service);
+        jobs.push(Job::new(i + 1, round(t), service));
+    }
+    jobs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(seed: u64, arrival: &str, service: &str, decimals: u32) -> WorkloadSpec {
+        WorkloadSpec { jobs: 2000, seed, arrival: ArrivalDist::parse(arrival).unwrap(), service: ServiceDist::parse(service).unwrap(), decimals }
+    }
+
+    fn triples(jobs: &[Job]) -> Vec<(usize, f64, f64)> {
+        jobs.iter().map(|j| (j.id, j.arrival, j.service)).collect()
+    }
+
+    fn gaps(jobs: &[Job]) -> Vec<f64> {
+        jobs.windows(2).map(|w| w[1].arrival - w[0].arrival).collect()
+    }
+
+    fn mean(xs: &[f64]) -> f64 {
+        xs.iter().sum::<f64>() / xs.len() as f64
+    }
+
+    #[test]
+    fn parse_checks_parameters() {
+        assert_eq!(ServiceDist::parse("uniform:1,5"), Ok(ServiceDist::Uniform { min: 1.0, max: 5.0 }));
+        assert_eq!(ServiceDist::parse("uniform:2,2"), Ok(ServiceDist::Uniform { min: 2.0, max: 2.0 }));
+        assert_eq!(ServiceDist::parse("uniform:5,1"), Err("uniform 的最小值 5 大于最大值 1".to_string()));
+        assert_eq!(ArrivalDist::parse("uniform:5,1"), Err("uniform 的最小值 5 大于最大值 1".to_string()));
+        assert!(ArrivalDist::parse("uniform:1").is_err());
+        assert!(ArrivalDist::parse("exp:-1").is_err());
+        assert!(ServiceDist::parse("pareto:1,0").is_err());
+        assert!(ServiceDist::parse("normal:1").is_err());
+        assert_eq!(ArrivalDist::parse("Bursty:0.5,10,1"), Ok(ArrivalDist::Bursty { size: 1.0, gap: 10.0, within: 1.0 }));
+        // Display 与 parse 互逆
+        let service = ServiceDist::parse("bimodal:1,10,0.7").unwrap();
+        assert_eq!(ServiceDist::parse(&service.to_string()), Ok(service));
+    }
+
+    #[test]
+    fn same_seed_same_workload() {
+        let a = generate(&spec(7, "bursty:4,10,0.5", "pareto:1,1.5", 1));
+        let b = generate(&spec(7, "bursty:4,10,0.5", "pareto:1,1.5", 1));
+        let c = generate(&spec(8, "bursty:4,10,0.5", "pareto:1,1.5", 1));
+        assert_eq!(triples(&a), triples(&b));
+        assert_ne!(triples(&a), triples(&c));
+        assert_eq!(a.iter().map(|j| j.id).collect::<Vec<_>>(), (1..=2000).collect::<Vec<_>>());
+        assert_eq!(a[0].arrival, 0.0);
+    }
+
+    #[test]
+    fn samples_respect_distribution_parameters() {
+        let jobs = generate(&spec(3, "uniform:1,3", "uniform:2,6", 0));
+        assert!(gaps(&jobs).iter().all(|&g| (1.0..=3.0).contains(&g)));
+        assert!(jobs.iter().all(|j| (2.0..=6.0).contains(&j.service) && j.service.fract() == 0.0));
+
+        let jobs = generate(&spec(3, "exp:2", "exp:4", 2));
+        assert!((mean(&gaps(&jobs)) - 2.0).abs() < 0.2);
+        assert!((mean(&jobs.iter().map(|j| j.service).collect::<Vec<_>>()) - 4.0).abs() < 0.4);
+        assert!(jobs.iter().all(|j| j.service >= 0.01 && ((j.service * 100.0).round() - j.service * 100.0).abs() < 1e-6));
+
+        // 双峰：短作业在 [0.75, 1.25]、长作业在 [7.5, 12.5]，约七成为短作业
+        let jobs = generate(&spec(3, "exp:1", "bimodal:1,10,0.7", 2));
+        let short = jobs.iter().filter(|j| j.service <= 1.25).count();
+        assert!(jobs.iter().all(|j| (0.75..=1.25).contains(&j.service) || (7.5..=12.5).contains(&j.service)));
+        assert!((short as f64 / 2000.0 - 0.7).abs() < 0.05);
+
+        let jobs = generate(&spec(3, "exp:1", "pareto:2,1.5", 2));
+        assert!(jobs.iter().all(|j| j.service >= 2.0));
+    }
+}
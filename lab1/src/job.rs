@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+
+use crate::scheduler::ScheduleError;
+
+// 一段连续执行：[start, end) 在第 channel 道上运行
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment {
+    pub channel: usize,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Job {
+    pub id: usize,
+    pub arrival: f64, // 到达时间，分钟
+    pub service: f64, // 估计运行时间，分钟
+    pub priority: i64, // 优先数，越小优先级越高
+    pub remaining: f64, // 剩余运行时间（被抢占时递减）
+    pub start: Option<f64>, // 首次开始运行时间
+    pub end: Option<f64>,
+    pub segments: Vec<Segment>, // 各执行段，非抢占调度下只有一段
+    pub level: usize, // 多级反馈队列中所在的队列（0 为最高优先级）
+    pub level_log: Vec<(f64, usize)>, // 所在队列的变化：(时刻, 队列)
+    pub aged: i64, // 老化累计提升的优先级（本次在就绪队列中等待期间）
+    pub enqueued: f64, // 最近一次进入就绪队列的时刻，老化从此时起计
+    pub memory: u64, // 所需主存，0 表示不占主存
+    pub admitted: Option<f64>, // 作业调度把它调入主存的时刻（仅在有主存限制时记录）
+    pub address: Option<u64>, // 所分得分区的起始地址
+    pub deadline: Option<f64>, // 绝对截止时间：须在此时刻前完成（与 arrival 同一时间轴）
+    pub period: Option<f64>, // 周期作业的周期；展开后每个实例都带有所属作业的周期
+    pub task: Option<usize>, // 周期作业展开后的实例所属的周期作业 id
+    pub tickets: u64, // 比例份额调度中持有的彩票数，缺省 1
+    pub pass: f64, // 步幅调度的行程值：每用完一个时间片增加一个步幅
+    pub grid: Option<u32>, // 刻度模式下的结果：每分钟的刻度数，周转、等待等时长按整刻度算出再换成分钟
+}
+
+impl Job {
+    pub fn new(id: usize, arrival: f64, service: f64) -> Self {
+        Self {
+            id,
+            arrival,
+            service,
+            priority: 0,
+            remaining: service,
+            start: None,
+            end: None,
+            segments: Vec::new(),
+            level: 0,
+            level_log: Vec::new(),
+            aged: 0,
+            enqueued: 0.0,
+            memory: 0,
+            admitted: None,
+            address: None,
+            deadline: None,
+            period: None,
+            task: None,
+            tickets: 1,
+            pass: 0.0,
+            grid: None,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: i64) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_memory(mut self, memory: u64) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Option<f64>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub fn with_period(mut self, period: Option<f64>) -> Self {
+        self.period = period;
+        self
+    }
+
+    pub fn with_tickets(mut self, tickets: u64) -> Self {
+        self.tickets = tickets;
+        self
+    }
+
+    pub fn with_grid(mut self, ticks: u32) -> Self {
+        self.grid = Some(ticks);
+        self
+    }
+
+    // 考虑老化后的有效优先数
+    pub fn effective_priority(&self) -> i64 {
+        self.priority - self.aged
+    }
+
+    // 时刻之差；刻度模式下两端都在刻度上，取整到刻度后只做一次除法，结果不带浮点尾差
+    fn span(&self, to: f64, from: f64) -> f64 {
+        match self.grid {
+            Some(ticks) => ((to - from) * ticks as f64).round() / ticks as f64,
+            None => to - from,
+        }
+    }
+
+    pub fn turnaround(&self) -> Option<f64> {
+        self.end.map(|e| self.span(e, self.arrival))
+    }
+
+    pub fn weighted_turnaround(&self) -> Option<f64> {
+        match (self.end, self.service, self.grid) {
+            (Some(e), s, Some(ticks)) if s > 0.0 => {
+                let ticks = ticks as f64;
+                Some(((e - self.arrival) * ticks).round() / (s * ticks).round())
+            }
+            (Some(e), s, None) if s > 0.0 => Some((e - self.arrival) / s),
+            _ => None,
+        }
+    }
+
+    // 等待时间：周转时间中没有在运行的部分（含被抢占后的等待与切换开销）
+    pub fn waiting(&self) -> Option<f64> {
+        self.end.map(|e| self.span(e - self.arrival, self.service))
+    }
+
+    // 响应时间：从到达到首次开始运行
+    pub fn response(&self) -> Option<f64> {
+        self.start.map(|s| self.span(s, self.arrival))
+    }
+
+    // 延迟：完成时刻减截止时间，提前完成时为负
+    pub fn lateness(&self) -> Option<f64> {
+        Some(self.span(self.end?, self.deadline?))
+    }
+
+    // 拖期：延迟的正部
+    pub fn tardiness(&self) -> Option<f64> {
+        self.lateness().map(|l| l.max(0.0))
+    }
+
+    // 是否错过截止时间（允许与截止时间相差浮点误差）
+    pub fn missed_deadline(&self) -> bool {
+        self.lateness().is_some_and(|l| l > 1e-9)
+    }
+}
+
+// 经过校验的作业流：id 唯一，到达时间与运行时间为有限数，到达时间非负，运行时间为正，
+// 截止时间（如有）不早于到达时间，周期（如有）为正，彩票数为正
+// clock 为 0 时刻对应的时钟时刻（当天零点起的分钟数），给出时结果按时钟时刻显示
+#[derive(Clone, Debug)]
+pub struct JobStream {
+    jobs: Vec<Job>,
+    clock: Option<f64>,
+}
+
+impl JobStream {
+    pub fn new(jobs: Vec<Job>) -> Result<Self, ScheduleError> {
+        let mut ids = HashSet::new();
+        for j in &jobs {
+            if !ids.insert(j.id) {
+                return Err(ScheduleError::DuplicateId(j.id));
+            }
+            if !j.arrival.is_finite() {
+                return Err(ScheduleError::NonFinite { id: j.id, field: "arrival" });
+            }
+            if !j.service.is_finite() {
+                return Err(ScheduleError::NonFinite { id: j.id, field: "service" });
+            }
+            if j.arrival < 0.0 {
+                return Err(ScheduleError::NegativeArrival { id: j.id, arrival: j.arrival });
+            }
+            if j.service <= 0.0 {
+                return Err(ScheduleError::NonPositiveService { id: j.id, service: j.service });
+            }
+            if let Some(deadline) = j.deadline {
+                if !deadline.is_finite() {
+                    return Err(ScheduleError::NonFinite { id: j.id, field: "deadline" });
+                }
+                if deadline < j.arrival {
+                    return Err(ScheduleError::DeadlineBeforeArrival { id: j.id, deadline, arrival: j.arrival });
+                }
+            }
+            if j.tickets == 0 {
+                return Err(ScheduleError::NoTickets(j.id));
+            }
+            if let Some(period) = j.period {
+                if !period.is_finite() {
+                    return Err(ScheduleError::NonFinite { id: j.id, field: "period" });
+                }
+                if period <= 0.0 {
+                    return Err(ScheduleError::NonPositivePeriod { id: j.id, period });
+                }
+            }
+        }
+        Ok(Self { jobs, clock: None })
+    }
+
+    pub fn with_clock(mut self, clock: Option<f64>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    pub fn clock(&self) -> Option<f64> {
+        self.clock
+    }
+}
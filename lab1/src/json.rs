@@ -0,0 +1,377 @@
+// 极简 JSON 支持：只覆盖作业流文件与结果导出需要的子集，不依赖第三方库
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    Arr(Vec<Value>),
+    Obj(Vec<(String, Value)>), // 保留字段原始顺序
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Obj(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+// 解析错误：行号从 1 开始
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub msg: String,
+}
+
+// 数组与对象的最大嵌套层数：作业流文件只有两三层，限制层数防止病态输入把递归下降的栈撑爆
+const MAX_DEPTH: usize = 64;
+
+pub fn parse(text: &str) -> Result<Value, ParseError> {
+    let mut p = Parser { src: text.as_bytes(), pos: 0, depth: 0 };
+    p.skip_ws();
+    let v = p.value()?;
+    p.skip_ws();
+    if p.pos < p.src.len() {
+        return Err(p.error("JSON 结尾存在多余内容"));
+    }
+    Ok(v)
+}
+
+struct Parser<'a> {
+    src: &'a [u8],
+    pos: usize,
+    depth: usize, // 当前所在的数组、对象层数
+}
+
+impl Parser<'_> {
+    fn error(&self, msg: &str) -> ParseError {
+        let line = self.src[..self.pos.min(self.src.len())].iter().filter(|&&c| c == b'\n').count() + 1;
+        ParseError { line, msg: msg.to_string() }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_ascii_whitespace() { self.pos += 1; } else { break; }
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ParseError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("期望 '{}'", c as char)))
+        }
+    }
+
+    fn literal(&mut self, word: &str, v: Value) -> Result<Value, ParseError> {
+        if self.src[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(v)
+        } else {
+            Err(self.error("无法识别的字面量"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        match self.peek() {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => Ok(Value::Str(self.string()?)),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(c) if c == b'-' || c.is_ascii_digit() => self.number(),
+            Some(_) => Err(self.error("无法识别的值")),
+            None => Err(self.error("JSON 意外结束")),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Value, ParseError>) -> Result<Value, ParseError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error(&format!("嵌套超过 {} 层", MAX_DEPTH)));
+        }
+        self.depth += 1;
+        let v = parse(self);
+        self.depth -= 1;
+        v
+    }
+
+    fn object(&mut self) -> Result<Value, ParseError> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        self.skip_ws();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Obj(fields));
+        }
+        loop {
+            self.skip_ws();
+            let key = self.string()?;
+            self.skip_ws();
+            self.expect(b':')?;
+            self.skip_ws();
+            let v = self.value()?;
+            fields.push((key, v));
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => { self.pos += 1; return Ok(Value::Obj(fields)); }
+                _ => return Err(self.error("对象中期望 ',' 或 '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, ParseError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_ws();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Arr(items));
+        }
+        loop {
+            self.skip_ws();
+            items.push(self.value()?);
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => { self.pos += 1; return Ok(Value::Arr(items)); }
+                _ => return Err(self.error("数组中期望 ',' 或 ']'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect(b'"')?;
+        let mut out: Vec<u8> = Vec::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("字符串未闭合")),
+                Some(b'"') => { self.pos += 1; break; }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = self.peek().ok_or_else(|| self.error("字符串未闭合"))?;
+                    self.pos += 1;
+                    match c {
+                        b'"' | b'\\' | b'/' => out.push(c),
+                        b'n' => out.push(b'\n'),
+                        b't' => out.push(b'\t'),
+                        b'r' => out.push(b'\r'),
+                        b'b' => out.push(0x08),
+                        b'f' => out.push(0x0c),
+                        b'u' => {
+                            let hex = self.src.get(self.pos..self.pos + 4).ok_or_else(|| self.error("\\u 转义不完整"))?;
+                            let code = std::str::from_utf8(hex).ok().and_then(|h| u32::from_str_radix(h, 16).ok());
+                            let ch = code.and_then(char::from_u32).ok_or_else(|| self.error("非法的 \\u 转义"))?;
+                            self.pos += 4;
+                            let mut buf = [0u8; 4];
+                            out.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+                        }
+                        _ => return Err(self.error("非法的转义字符")),
+                    }
+                }
+                Some(c) => { out.push(c); self.pos += 1; }
+            }
+        }
+        String::from_utf8(out).map_err(|_| self.error("字符串不是合法的 UTF-8"))
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let begin = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() || matches!(c, b'-' | b'+' | b'.' | b'e' | b'E') { self.pos += 1; } else { break; }
+        }
+        let text = std::str::from_utf8(&self.src[begin..self.pos]).unwrap_or("");
+        text.parse::<f64>().map(Value::Num).map_err(|_| self.error(&format!("非法数字 '{}'", text)))
+    }
+}
+
+impl From<f64> for Value {
+    // 非有限数在 JSON 中没有表示，记为 null
+    fn from(v: f64) -> Self {
+        if v.is_finite() { Value::Num(v) } else { Value::Null }
+    }
+}
+
+impl From<usize> for Value {
+    fn from(v: usize) -> Self {
+        Value::Num(v as f64)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(v: i64) -> Self {
+        Value::Num(v as f64)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::Str(v.to_string())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Value::Null, Into::into)
+    }
+}
+
+impl Value {
+    // 带两空格缩进的输出；只含标量的数组、以及不超过 3 个标量字段的对象写在一行内
+    pub fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, 0);
+        out.push('\n');
+        out
+    }
+
+    // 不换行、不加空格的输出，用于 JSON lines（每行一个值）
+    pub fn to_compact(&self) -> String {
+        let mut out = String::new();
+        self.write_compact(&mut out);
+        out
+    }
+
+    fn write_compact(&self, out: &mut String) {
+        match self {
+            Value::Arr(items) => {
+                out.push('[');
+                for (i, v) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    v.write_compact(out);
+                }
+                out.push(']');
+            }
+            Value::Obj(fields) => {
+                out.push('{');
+                for (i, (k, v)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_str(out, k);
+                    out.push(':');
+                    v.write_compact(out);
+                }
+                out.push('}');
+            }
+            scalar => scalar.write(out, 0),
+        }
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, Value::Arr(_) | Value::Obj(_))
+    }
+
+    fn write(&self, out: &mut String, indent: usize) {
+        let newline = |out: &mut String, level: usize| {
+            out.push('\n');
+            out.push_str(&"  ".repeat(level));
+        };
+        match self {
+            Value::Null => out.push_str("null"),
+            Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Value::Num(n) => out.push_str(&n.to_string()),
+            Value::Str(s) => write_str(out, s),
+            Value::Arr(items) => {
+                out.push('[');
+                let inline = items.iter().all(Value::is_scalar);
+                for (i, v) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(if inline { ", " } else { "," });
+                    }
+                    if !inline {
+                        newline(out, indent + 1);
+                    }
+                    v.write(out, indent + 1);
+                }
+                if !inline && !items.is_empty() {
+                    newline(out, indent);
+                }
+                out.push(']');
+            }
+            Value::Obj(fields) if fields.len() <= 3 && fields.iter().all(|(_, v)| v.is_scalar()) => {
+                let inner: Vec<String> = fields
+                    .iter()
+                    .map(|(k, v)| {
+                        let mut item = String::new();
+                        write_str(&mut item, k);
+                        item.push_str(": ");
+                        v.write(&mut item, indent + 1);
+                        item
+                    })
+                    .collect();
+                out.push_str(&format!("{{ {} }}", inner.join(", ")));
+            }
+            Value::Obj(fields) => {
+                out.push('{');
+                for (i, (k, v)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, indent + 1);
+                    write_str(out, k);
+                    out.push_str(": ");
+                    v.write(out, indent + 1);
+                }
+                if !fields.is_empty() {
+                    newline(out, indent);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        let err = parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert_eq!((err.line, err.msg.as_str()), (1, "嵌套超过 64 层"));
+        // 远超限制的输入同样报错而不是栈溢出
+        assert!(parse(&"[{\"a\":".repeat(100_000)).is_err());
+    }
+
+    #[test]
+    fn parses_values_and_reports_lines() {
+        let v = parse("{ \"jobs\": [1, -2.5e1, \"a\\u0062\\n\", true, null] }").unwrap();
+        let Some(Value::Arr(items)) = v.get("jobs") else { panic!("{:?}", v) };
+        assert!(matches!(items[..], [Value::Num(a), Value::Num(b), Value::Str(ref s), Value::Bool(true), Value::Null]
+            if a == 1.0 && b == -25.0 && s == "ab\n"));
+        let err = parse("[1,\n2,\n]").unwrap_err();
+        assert_eq!((err.line, err.msg.as_str()), (3, "无法识别的值"));
+        assert_eq!(parse("[1] 2").unwrap_err().msg, "JSON 结尾存在多余内容");
+        assert_eq!(parse("[1.2.3]").unwrap_err().msg, "非法数字 '1.2.3'");
+    }
+}
@@ -0,0 +1,66 @@
+// LaTeX 表格导出：与 report/main.tex 中手写表格的列布局一致，
+// 生成的文件可直接在报告的 table 环境中 \input{}
+
+use crate::experiment::{Experiment, Summary};
+use crate::result::ScheduleResult;
+
+fn opt(v: Option<f64>) -> String {
+    v.map_or("--".to_string(), |v| format!("{:.2}", v))
+}
+
+// id, arr, serv, start, end, turn, wturn 七列，最后一行为平均值；有时钟起点时 arr/start/end 写成时刻
+pub fn render_tabular(result: &ScheduleResult) -> String {
+    let time = |t: Option<f64>| match result.clock {
+        Some(_) => result.clock_time(t).unwrap_or_else(|| "--".to_string()),
+        None => opt(t),
+    };
+    let mut out = String::new();
+    out.push_str(&format!("% 由 lab1 生成：{}，m = {}，请勿手工修改\n", result.algorithm, result.m()));
+    out.push_str("\\begin{tabular}{c|c|c|c|c|c|c}\n");
+    out.push_str("id & arr & serv & start & end & turn & wturn \\\\\n");
+    out.push_str("\\hline\n");
+    for r in &result.jobs {
+        out.push_str(&format!(
+            "{} & {} & {:.2} & {} & {} & {} & {} \\\\\n",
+            r.id,
+            time(Some(r.arrival)),
+            r.service,
+            time(r.start),
+            time(r.end),
+            opt(r.turnaround),
+            opt(r.weighted_turnaround)
+        ));
+    }
+    out.push_str("\\hline\n");
+    if let (Some(t), Some(w)) = (result.metrics.avg_turnaround, result.metrics.avg_weighted_turnaround) {
+        out.push_str(&format!("\\multicolumn{{7}}{{r}}{{\\small 平均周转时间 = {:.4}，带权平均周转时间 = {:.4}}}\n", t, w));
+    }
+    out.push_str("\\end{tabular}\n");
+    out
+}
+
+// 批量实验汇总：每个配置一行，给出均值 ± 95% 置信区间半宽与标准差
+pub fn render_experiment(exp: &Experiment) -> String {
+    let cell = |s: Option<Summary>| match s {
+        Some(Summary { mean, ci95: Some(ci), std_dev, .. }) => format!("{:.2} $\\pm$ {:.2} & {}", mean, ci, opt(std_dev)),
+        Some(s) => format!("{:.2} & --", s.mean),
+        None => "-- & --".to_string(),
+    };
+    let mut out = String::new();
+    out.push_str(&format!("% 由 lab1 生成：{}，请勿手工修改\n", exp.describe()));
+    out.push_str("\\begin{tabular}{l|c|c|c|c|c|c}\n");
+    out.push_str("算法 & m & 平均周转 & 标准差 & 带权平均周转 & 标准差 & 最优次数 \\\\\n");
+    out.push_str("\\hline\n");
+    for r in &exp.rows {
+        out.push_str(&format!(
+            "{} & {} & {} & {} & {} \\\\\n",
+            r.algorithm.replace('%', "\\%"),
+            r.channels,
+            cell(r.turnaround),
+            cell(r.weighted_turnaround),
+            r.wins
+        ));
+    }
+    out.push_str("\\end{tabular}\n");
+    out
+}
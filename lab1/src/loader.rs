@@ -0,0 +1,546 @@
+// 作业流文件加载：支持 CSV / TOML / JSON 三种格式
+// 每条记录至少包含 id、arrival、service 三个字段，其余字段（如 priority、memory）可选，未识别的字段忽略
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::clock;
+use crate::job::Job;
+use crate::json;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Toml,
+    Json,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Some(Format::Csv),
+            "toml" => Some(Format::Toml),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    // 根据扩展名推断格式
+    pub fn from_path(path: &Path) -> Option<Format> {
+        Format::parse(path.extension()?.to_str()?)
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    UnknownFormat(String),
+    Syntax { line: usize, msg: String },
+    MissingField { record: usize, field: &'static str },
+    InvalidValue { record: usize, field: &'static str, msg: String },
+    DuplicateId(usize),
+    Empty,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "读取文件失败：{}", e),
+            LoadError::UnknownFormat(p) => write!(f, "无法从扩展名判断文件格式（支持 .csv/.toml/.json）：{}", p),
+            LoadError::Syntax { line, msg } => write!(f, "第 {} 行语法错误：{}", line, msg),
+            LoadError::MissingField { record, field } => write!(f, "第 {} 条记录缺少字段 {}", record, field),
+            LoadError::InvalidValue { record, field, msg } => write!(f, "第 {} 条记录字段 {} 非法：{}", record, field, msg),
+            LoadError::DuplicateId(id) => write!(f, "作业 id {} 重复", id),
+            LoadError::Empty => write!(f, "作业流为空"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+// 记录中的单个字段值
+#[derive(Clone, Debug)]
+enum Scalar {
+    Num(f64),
+    Str(String),
+    Bool(bool),
+}
+
+// 一条记录：字段名 -> 值（字段名统一小写）
+type Record = Vec<(String, Scalar)>;
+
+// 读入的作业流。到达时间写成时钟时刻（如 8:50）时，origin 为最早的到达时刻（当天零点起的分钟数），
+// 各作业的 arrival 换算为相对 origin 的分钟数；到达时间为普通数字时 origin 为 None
+#[derive(Clone, Debug)]
+pub struct JobFile {
+    pub jobs: Vec<Job>,
+    pub origin: Option<f64>,
+}
+
+pub fn load_jobs(path: &Path) -> Result<JobFile, LoadError> {
+    let format = Format::from_path(path).ok_or_else(|| LoadError::UnknownFormat(path.display().to_string()))?;
+    let text = fs::read_to_string(path)?;
+    parse_jobs(&text, format)
+}
+
+pub fn parse_jobs(text: &str, format: Format) -> Result<JobFile, LoadError> {
+    let records = match format {
+        Format::Csv => parse_csv(text)?,
+        Format::Toml => parse_toml(text)?,
+        Format::Json => parse_json(text)?,
+    };
+    build_jobs(&records)
+}
+
+// 记录 -> 作业，并做基本校验；同一文件中的到达时间要么都是时钟时刻，要么都是分钟数
+fn build_jobs(records: &[Record]) -> Result<JobFile, LoadError> {
+    if records.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    let mut seen = HashSet::new();
+    let mut jobs = Vec::with_capacity(records.len());
+    let mut clock_arrivals = None; // 第一条记录决定到达时间的写法
+    for (i, rec) in records.iter().enumerate() {
+        let no = i + 1;
+        let id = field_num(rec, no, "id")?;
+        if id < 0.0 || id.fract() != 0.0 {
+            return Err(LoadError::InvalidValue { record: no, field: "id", msg: format!("{} 不是非负整数", id) });
+        }
+        let clock_time = match lookup(rec, "arrival") {
+            Some(Scalar::Str(s)) if s.contains(':') => Some(
+                clock::parse(s)
+                    .ok_or_else(|| LoadError::InvalidValue { record: no, field: "arrival", msg: format!("'{}' 不是 H:MM 时刻", s) })?,
+            ),
+            _ => None,
+        };
+        if *clock_arrivals.get_or_insert(clock_time.is_some()) != clock_time.is_some() {
+            return Err(LoadError::InvalidValue { record: no, field: "arrival", msg: "时钟时刻不能与分钟数混用".to_string() });
+        }
+        let arrival = match clock_time {
+            Some(t) => t,
+            None => field_num(rec, no, "arrival")?,
+        };
+        if arrival < 0.0 {
+            return Err(LoadError::InvalidValue { record: no, field: "arrival", msg: format!("{} 为负数", arrival) });
+        }
+        let service = field_num(rec, no, "service")?;
+        if service <= 0.0 {
+            return Err(LoadError::InvalidValue { record: no, field: "service", msg: format!("{} 必须为正数", service) });
+        }
+        let id = id as usize;
+        if !seen.insert(id) {
+            return Err(LoadError::DuplicateId(id));
+        }
+        let priority = match lookup(rec, "priority") {
+            Some(_) => {
+                let p = field_num(rec, no, "priority")?;
+                if p.fract() != 0.0 {
+                    return Err(LoadError::InvalidValue { record: no, field: "priority", msg: format!("{} 不是整数", p) });
+                }
+                p as i64
+            }
+            None => 0,
+        };
+        let memory = match lookup(rec, "memory") {
+            Some(_) => {
+                let v = field_num(rec, no, "memory")?;
+                if v < 0.0 || v.fract() != 0.0 {
+                    return Err(LoadError::InvalidValue { record: no, field: "memory", msg: format!("{} 不是非负整数", v) });
+                }
+                v as u64
+            }
+            None => 0,
+        };
+        // 截止时间与到达时间写法相同（时钟时刻或分钟数）；周期为时长，总是分钟数
+        let deadline = match lookup(rec, "deadline") {
+            Some(Scalar::Str(s)) if s.contains(':') => match (clock_time, clock::parse(s)) {
+                (Some(_), Some(t)) => Some(t),
+                (None, _) => return Err(LoadError::InvalidValue { record: no, field: "deadline", msg: "到达时间不是时钟时刻".to_string() }),
+                (_, None) => return Err(LoadError::InvalidValue { record: no, field: "deadline", msg: format!("'{}' 不是 H:MM 时刻", s) }),
+            },
+            Some(_) if clock_time.is_some() => {
+                return Err(LoadError::InvalidValue { record: no, field: "deadline", msg: "应与到达时间一样写成时钟时刻".to_string() });
+            }
+            Some(_) => Some(field_num(rec, no, "deadline")?),
+            None => None,
+        };
+        if deadline.is_some_and(|d| d < arrival) {
+            return Err(LoadError::InvalidValue { record: no, field: "deadline", msg: "早于到达时间".to_string() });
+        }
+        let period = match lookup(rec, "period") {
+            Some(_) => {
+                let p = field_num(rec, no, "period")?;
+                if p <= 0.0 {
+                    return Err(LoadError::InvalidValue { record: no, field: "period", msg: format!("{} 必须为正数", p) });
+                }
+                Some(p)
+            }
+            None => None,
+        };
+        let tickets = match lookup(rec, "tickets") {
+            Some(_) => {
+                let v = field_num(rec, no, "tickets")?;
+                if v < 1.0 || v.fract() != 0.0 {
+                    return Err(LoadError::InvalidValue { record: no, field: "tickets", msg: format!("{} 不是正整数", v) });
+                }
+                v as u64
+            }
+            None => 1,
+        };
+        jobs.push(
+            Job::new(id, arrival, service)
+                .with_priority(priority)
+                .with_memory(memory)
+                .with_deadline(deadline)
+                .with_period(period)
+                .with_tickets(tickets),
+        );
+    }
+    let origin = (clock_arrivals == Some(true)).then(|| jobs.iter().map(|j| j.arrival).fold(f64::INFINITY, f64::min));
+    if let Some(origin) = origin {
+        for j in jobs.iter_mut() {
+            j.arrival -= origin;
+            if let Some(d) = j.deadline.as_mut() {
+                *d -= origin;
+            }
+        }
+    }
+    Ok(JobFile { jobs, origin })
+}
+
+fn lookup<'a>(rec: &'a Record, field: &str) -> Option<&'a Scalar> {
+    rec.iter().find(|(k, _)| k == field).map(|(_, v)| v)
+}
+
+fn field_num(rec: &Record, no: usize, field: &'static str) -> Result<f64, LoadError> {
+    let v = match lookup(rec, field) {
+        Some(Scalar::Num(v)) => *v,
+        Some(Scalar::Str(s)) => s.trim().parse::<f64>().map_err(|_| LoadError::InvalidValue { record: no, field, msg: format!("'{}' 不是数字", s) })?,
+        Some(Scalar::Bool(b)) => return Err(LoadError::InvalidValue { record: no, field, msg: format!("{} 不是数字", b) }),
+        None => return Err(LoadError::MissingField { record: no, field }),
+    };
+    if !v.is_finite() {
+        return Err(LoadError::InvalidValue { record: no, field, msg: format!("{} 不是有限数", v) });
+    }
+    Ok(v)
+}
+
+// 文本标量：数字 / 布尔 / 字符串（去掉引号）
+fn scalar_from_text(raw: &str) -> Scalar {
+    let s = raw.trim();
+    if s.len() >= 2 && ((s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\''))) {
+        return Scalar::Str(s[1..s.len() - 1].to_string());
+    }
+    match s {
+        "true" => Scalar::Bool(true),
+        "false" => Scalar::Bool(false),
+        _ => match s.replace('_', "").parse::<f64>() {
+            Ok(v) => Scalar::Num(v),
+            Err(_) => Scalar::Str(s.to_string()),
+        },
+    }
+}
+
+// CSV：首行若不是数字则视为表头；无表头时按 id,arrival,service 的顺序解释
+// 以 # 开头的行为注释
+fn parse_csv(text: &str) -> Result<Vec<Record>, LoadError> {
+    let default_header = ["id", "arrival", "service"];
+    let mut header: Option<Vec<String>> = None;
+    let mut records = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let cells: Vec<&str> = line.split(',').map(|c| c.trim()).collect();
+        if header.is_none() && records.is_empty() && cells[0].trim_matches('"').parse::<f64>().is_err() {
+            header = Some(cells.iter().map(|c| c.trim_matches('"').to_ascii_lowercase()).collect());
+            continue;
+        }
+        let names: Vec<String> = match &header {
+            Some(h) => h.clone(),
+            None => default_header.iter().map(|s| s.to_string()).collect(),
+        };
+        if cells.len() < names.len().min(default_header.len()) {
+            return Err(LoadError::Syntax { line: i + 1, msg: format!("列数不足：{}", line) });
+        }
+        let rec: Record = names
+            .into_iter()
+            .zip(cells.iter())
+            .filter(|(_, c)| !c.is_empty())
+            .map(|(k, c)| (k, scalar_from_text(c)))
+            .collect();
+        records.push(rec);
+    }
+    Ok(records)
+}
+
+// TOML：仅支持 [[jobs]]（或 [[job]]）表数组，每个表内为 key = value
+// 其余表与顶层键被忽略
+fn parse_toml(text: &str) -> Result<Vec<Record>, LoadError> {
+    let mut records: Vec<Record> = Vec::new();
+    let mut in_jobs = false;
+    for (i, raw) in text.lines().enumerate() {
+        let line = strip_toml_comment(raw).trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            let name = line.trim_matches(|c| c == '[' || c == ']').trim();
+            in_jobs = line.starts_with("[[") && (name == "jobs" || name == "job");
+            if in_jobs {
+                records.push(Vec::new());
+            }
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or_else(|| LoadError::Syntax { line: i + 1, msg: format!("期望 key = value：{}", line) })?;
+        if in_jobs {
+            let key = key.trim().trim_matches('"').to_ascii_lowercase();
+            if let Some(rec) = records.last_mut() {
+                rec.push((key, scalar_from_text(value)));
+            }
+        }
+    }
+    Ok(records)
+}
+
+// 去掉 # 注释（不处理引号内的 #）
+fn strip_toml_comment(line: &str) -> &str {
+    let mut in_str = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_str = !in_str,
+            '#' if !in_str => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+// JSON：顶层为作业对象数组，或带 "jobs" 数组的对象
+fn parse_json(text: &str) -> Result<Vec<Record>, LoadError> {
+    let root = json::parse(text).map_err(|e| LoadError::Syntax { line: e.line, msg: e.msg })?;
+    let items = match root.get("jobs").unwrap_or(&root) {
+        json::Value::Arr(items) => items,
+        _ => return Err(LoadError::Syntax { line: 1, msg: "顶层应为作业数组或包含 jobs 数组的对象".to_string() }),
+    };
+    let mut records = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        let json::Value::Obj(fields) = item else {
+            return Err(LoadError::InvalidValue { record: i + 1, field: "jobs", msg: "记录必须是对象".to_string() });
+        };
+        let rec: Record = fields
+            .iter()
+            .filter_map(|(k, v)| {
+                let s = match v {
+                    json::Value::Num(n) => Scalar::Num(*n),
+                    json::Value::Str(s) => Scalar::Str(s.clone()),
+                    json::Value::Bool(b) => Scalar::Bool(*b),
+                    _ => return None,
+                };
+                Some((k.to_ascii_lowercase(), s))
+            })
+            .collect();
+        records.push(rec);
+    }
+    Ok(records)
+}
+
+// 作业流 -> 文本，与 parse_jobs 互逆；所有作业优先数都为 0 时省略 priority 字段，都不占主存时省略 memory 字段，
+// 都没有截止时间、周期时省略 deadline、period 字段（CSV 中个别作业没有时留空），彩票数都为 1 时省略 tickets 字段
+pub fn format_jobs(jobs: &[Job], format: Format) -> String {
+    let with_priority = jobs.iter().any(|j| j.priority != 0);
+    let with_memory = jobs.iter().any(|j| j.memory != 0);
+    let with_deadline = jobs.iter().any(|j| j.deadline.is_some());
+    let with_period = jobs.iter().any(|j| j.period.is_some());
+    let with_tickets = jobs.iter().any(|j| j.tickets != 1);
+    let cell = |v: Option<f64>| v.map_or(String::new(), |v| v.to_string());
+    let mut out = String::new();
+    match format {
+        Format::Csv => {
+            out.push_str("id,arrival,service");
+            out.push_str(if with_priority { ",priority" } else { "" });
+            out.push_str(if with_memory { ",memory" } else { "" });
+            out.push_str(if with_deadline { ",deadline" } else { "" });
+            out.push_str(if with_period { ",period" } else { "" });
+            out.push_str(if with_tickets { ",tickets\n" } else { "\n" });
+            for j in jobs {
+                out.push_str(&format!("{},{},{}", j.id, j.arrival, j.service));
+                if with_priority {
+                    out.push_str(&format!(",{}", j.priority));
+                }
+                if with_memory {
+                    out.push_str(&format!(",{}", j.memory));
+                }
+                if with_deadline {
+                    out.push_str(&format!(",{}", cell(j.deadline)));
+                }
+                if with_period {
+                    out.push_str(&format!(",{}", cell(j.period)));
+                }
+                if with_tickets {
+                    out.push_str(&format!(",{}", j.tickets));
+                }
+                out.push('\n');
+            }
+        }
+        Format::Toml => {
+            for (i, j) in jobs.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                out.push_str(&format!("[[jobs]]\nid = {}\narrival = {}\nservice = {}\n", j.id, j.arrival, j.service));
+                if with_priority {
+                    out.push_str(&format!("priority = {}\n", j.priority));
+                }
+                if with_memory {
+                    out.push_str(&format!("memory = {}\n", j.memory));
+                }
+                if let Some(deadline) = j.deadline {
+                    out.push_str(&format!("deadline = {}\n", deadline));
+                }
+                if let Some(period) = j.period {
+                    out.push_str(&format!("period = {}\n", period));
+                }
+                if with_tickets {
+                    out.push_str(&format!("tickets = {}\n", j.tickets));
+                }
+            }
+        }
+        Format::Json => {
+            out.push_str("{\n  \"jobs\": [\n");
+            for (i, j) in jobs.iter().enumerate() {
+                let sep = if i + 1 < jobs.len() { "," } else { "" };
+                let priority = if with_priority { format!(", \"priority\": {}", j.priority) } else { String::new() };
+                let memory = if with_memory { format!(", \"memory\": {}", j.memory) } else { String::new() };
+                let deadline = j.deadline.map_or(String::new(), |d| format!(", \"deadline\": {}", d));
+                let period = j.period.map_or(String::new(), |p| format!(", \"period\": {}", p));
+                let tickets = if with_tickets { format!(", \"tickets\": {}", j.tickets) } else { String::new() };
+                out.push_str(&format!(
+                    "    {{ \"id\": {}, \"arrival\": {}, \"service\": {}{}{}{}{}{} }}{}\n",
+                    j.id, j.arrival, j.service, priority, memory, deadline, period, tickets, sep
+                ));
+            }
+            out.push_str("  ]\n}\n");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triples(file: &JobFile) -> Vec<(usize, f64, f64)> {
+        file.jobs.iter().map(|j| (j.id, j.arrival, j.service)).collect()
+    }
+
+    fn invalid(text: &str, format: Format) -> (usize, &'static str) {
+        match parse_jobs(text, format) {
+            Err(LoadError::InvalidValue { record, field, .. }) => (record, field),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn csv_with_and_without_header() {
+        let bare = parse_jobs("1,0,3\n2,2,6\n", Format::Csv).unwrap();
+        assert_eq!(triples(&bare), [(1, 0.0, 3.0), (2, 2.0, 6.0)]);
+        // 表头可以调换列序、带引号和大小写，空单元格视为缺省
+        let headed = parse_jobs("\"Service\",ID,arrival,priority\n3,1,0,\n6,2,2,-1\n", Format::Csv).unwrap();
+        assert_eq!(triples(&headed), triples(&bare));
+        assert_eq!(headed.jobs.iter().map(|j| j.priority).collect::<Vec<_>>(), [0, -1]);
+        assert!(bare.origin.is_none());
+    }
+
+    #[test]
+    fn comments_are_skipped() {
+        let csv = parse_jobs("# 作业流\nid,arrival,service\n\n# 第一个\n1,0,3\n", Format::Csv).unwrap();
+        let toml = parse_jobs(
+            "# 作业流\ntitle = \"x # y\"\n[meta]\nid = 9\n\n[[jobs]] # 第一个\nid = 1\narrival = 0 # 分钟\nservice = 3\n",
+            Format::Toml,
+        )
+        .unwrap();
+        assert_eq!(triples(&csv), [(1, 0.0, 3.0)]);
+        assert_eq!(triples(&toml), [(1, 0.0, 3.0)]);
+    }
+
+    #[test]
+    fn json_array_or_jobs_object() {
+        let array = parse_jobs("[{\"id\": 1, \"arrival\": 0, \"service\": 3, \"note\": [1]}]", Format::Json).unwrap();
+        let object = parse_jobs("{\"jobs\": [{\"ID\": 1, \"arrival\": \"0\", \"service\": 3}]}", Format::Json).unwrap();
+        assert_eq!(triples(&array), [(1, 0.0, 3.0)]);
+        assert_eq!(triples(&object), [(1, 0.0, 3.0)]);
+    }
+
+    #[test]
+    fn clock_arrivals_are_relative_to_the_earliest() {
+        let csv = parse_jobs("id,arrival,service,deadline\n1,9:10,20,10:00\n2,8:50,10,\n", Format::Csv).unwrap();
+        let toml = parse_jobs("[[jobs]]\nid = 1\narrival = \"9:10\"\nservice = 20\ndeadline = \"10:00\"\n[[jobs]]\nid = 2\narrival = \"8:50\"\nservice = 10\n", Format::Toml).unwrap();
+        let json = parse_jobs("[{\"id\": 1, \"arrival\": \"9:10\", \"service\": 20, \"deadline\": \"10:00\"}, {\"id\": 2, \"arrival\": \"8:50\", \"service\": 10}]", Format::Json).unwrap();
+        for file in [csv, toml, json] {
+            assert_eq!(file.origin, Some(530.0));
+            assert_eq!(triples(&file), [(1, 20.0, 20.0), (2, 0.0, 10.0)]);
+            assert_eq!(file.jobs[0].deadline, Some(70.0));
+        }
+        assert_eq!(invalid("1,8:50,3\n2,10,3\n", Format::Csv), (2, "arrival"));
+        assert_eq!(invalid("1,8:75,3\n", Format::Csv), (1, "arrival"));
+        assert_eq!(invalid("id,arrival,service,deadline\n1,8:50,3,100\n", Format::Csv), (1, "deadline"));
+        assert_eq!(invalid("id,arrival,service,deadline\n1,0,3,9:00\n", Format::Csv), (1, "deadline"));
+    }
+
+    #[test]
+    fn malformed_rows_are_syntax_errors() {
+        let syntax = |text: &str, format| match parse_jobs(text, format) {
+            Err(LoadError::Syntax { line, .. }) => line,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(syntax("id,arrival,service\n1,0,3\n2,5\n", Format::Csv), 3);
+        assert_eq!(syntax("[[jobs]]\nid = 1\narrival 0\n", Format::Toml), 3);
+        assert_eq!(syntax("[{\"id\": 1,\n\"arrival\": 0,\n}]", Format::Json), 3);
+        assert_eq!(syntax("{\"jobs\": 1}", Format::Json), 1);
+        assert_eq!(invalid("[1, 2]", Format::Json), (1, "jobs"));
+        assert!(matches!(parse_jobs("# 只有注释\n", Format::Csv), Err(LoadError::Empty)));
+    }
+
+    #[test]
+    fn malformed_values_name_record_and_field() {
+        assert_eq!(invalid("1,0,3\n2,x,3\n", Format::Csv), (2, "arrival"));
+        assert_eq!(invalid("1.5,0,3\n", Format::Csv), (1, "id"));
+        assert_eq!(invalid("1,-1,3\n", Format::Csv), (1, "arrival"));
+        assert_eq!(invalid("1,0,0\n", Format::Csv), (1, "service"));
+        assert_eq!(invalid("id,arrival,service,priority\n1,0,3,0.5\n", Format::Csv), (1, "priority"));
+        assert_eq!(invalid("id,arrival,service,memory\n1,0,3,-4\n", Format::Csv), (1, "memory"));
+        assert_eq!(invalid("id,arrival,service,tickets\n1,0,3,0\n", Format::Csv), (1, "tickets"));
+        assert_eq!(invalid("id,arrival,service,period\n1,0,3,0\n", Format::Csv), (1, "period"));
+        assert_eq!(invalid("id,arrival,service,deadline\n1,5,3,4\n", Format::Csv), (1, "deadline"));
+        assert_eq!(invalid("[[jobs]]\nid = 1\narrival = true\nservice = 3\n", Format::Toml), (1, "arrival"));
+        assert_eq!(invalid("[{\"id\": 1, \"arrival\": 0, \"service\": 1e999}]", Format::Json), (1, "service"));
+        assert!(matches!(
+            parse_jobs("[[jobs]]\nid = 1\narrival = 0\n", Format::Toml),
+            Err(LoadError::MissingField { record: 1, field: "service" })
+        ));
+        assert!(matches!(parse_jobs("1,0,3\n1,2,3\n", Format::Csv), Err(LoadError::DuplicateId(1))));
+    }
+
+    #[test]
+    fn format_and_parse_round_trip() {
+        let jobs = vec![
+            Job::new(1, 0.0, 3.5).with_priority(2).with_memory(10).with_deadline(Some(9.0)).with_tickets(3),
+            Job::new(2, 1.25, 4.0).with_period(Some(20.0)),
+        ];
+        for format in [Format::Csv, Format::Toml, Format::Json] {
+            let back = parse_jobs(&format_jobs(&jobs, format), format).unwrap();
+            let fields = |j: &Job| (j.id, j.arrival, j.service, j.priority, j.memory, j.deadline, j.period, j.tickets);
+            assert_eq!(back.jobs.iter().map(fields).collect::<Vec<_>>(), jobs.iter().map(fields).collect::<Vec<_>>(), "{:?}", format);
+        }
+    }
+}
@@ -1,278 +1,161 @@
-use std::cmp::Ordering;
-
-#[derive(Clone, Debug)]
-struct Job {
-    id: usize,
-    arrival: f64, // 到达时间，分钟
-    service: f64, // 估计运行时间，分钟
-    start: Option<f64>,
-    end: Option<f64>,
-}
-
-impl Job {
-    fn new(id: usize, arrival: f64, service: f64) -> Self {
-        Self { id, arrival, service, start: None, end: None }
-    }
-
-    fn turnaround(&self) -> Option<f64> {
-        match (self.end, Some(self.arrival)) {
-            (Some(e), Some(a)) => Some(e - a),
-            _ => None,
-        }
-    }
-
-    fn weighted_turnaround(&self) -> Option<f64> {
-        match (self.turnaround(), self.service) {
-            (Some(t), s) if s > 0.0 => Some(t / s),
-            _ => None,
-        }
-    }
+use std::path::Path;
+use std::process;
+
+mod cli;
+mod clock;
+mod experiment;
+mod explain;
+mod gantt;
+mod generator;
+mod job;
+mod json;
+mod latex;
+mod loader;
+mod memory;
+mod queue;
+mod realtime;
+mod result;
+mod rng;
+mod scheduler;
+mod share;
+mod trace;
+
+use cli::{Command, CompareOpts, ExperimentOpts, GenerateOpts, OutputFormat, RunOpts, Sample, Workload};
+use experiment::Summary;
+use job::{Job, JobStream};
+use result::{Metrics, ScheduleResult, SLOWDOWN_BUCKETS};
+use realtime::Analysis;
+use scheduler::{Algorithm, Fcfs, Hrrn, ScheduleError, Scheduler, SchedulerConfig, SimOptions, Sjf};
+use trace::Trace;
+
+// 缺失的值（未开始或未完成）显示为 -
+fn opt(v: Option<f64>) -> String {
+    v.map_or("-".to_string(), |v| format!("{:.2}", v))
 }
 
 // 结果打印辅助
-fn print_results(mut jobs: Vec<Job>, title: &str) {
-    jobs.sort_by(|a, b| a.id.cmp(&b.id));
+// starvation 给出时，等待时间超过该值的作业在表中以 * 标出；
+// clock 给出时，到达/开始/结束时间与执行段按时钟时刻（0 时刻对应 clock）显示
+fn print_results(mut jobs: Vec<Job>, m: usize, title: &str, starvation: Option<f64>, clock: Option<f64>) {
+    jobs.sort_by_key(|j| j.id);
+    let at = |t: Option<f64>| match (clock, t) {
+        (Some(c), Some(t)) => clock::format(c + t),
+        _ => opt(t),
+    };
     println!("\n=== {} ===", title);
-    println!("id\tarr\tserv\tstart\tend\tturn\twturn");
-    let mut sum_turn = 0.0;
-    let mut sum_wturn = 0.0;
-    let mut count = 0.0;
+    if let Some(c) = clock {
+        println!("（时刻按时钟显示，0 时刻 = {}；周转、等待等时长仍以分钟计）", clock::format(c));
+    }
+    println!("id\tarr\tserv\tprio\tstart\tend\tturn\twturn\twait\tresp");
     for j in &jobs {
-        let start = j.start.map_or(-1.0, |v| v);
-        let end = j.end.map_or(-1.0, |v| v);
-        let turn = j.turnaround().unwrap_or(-1.0);
-        let wturn = j.weighted_turnaround().unwrap_or(-1.0);
-        println!("{}\t{:.2}\t{:.2}\t{:.2}\t{:.2}\t{:.2}\t{:.2}", j.id, j.arrival, j.service, start, end, turn, wturn);
-        if turn >= 0.0 {
-            sum_turn += turn;
-            sum_wturn += wturn.max(0.0);
-            count += 1.0;
-        }
+        let mark = if starvation.is_some_and(|t| j.waiting().is_some_and(|w| w > t)) { "*" } else { "" };
+        println!(
+            "{}\t{}\t{:.2}\t{}\t{}\t{}\t{}\t{}\t{}{}\t{}",
+            j.id,
+            at(Some(j.arrival)),
+            j.service,
+            j.priority,
+            at(j.start),
+            at(j.end),
+            opt(j.turnaround()),
+            opt(j.weighted_turnaround()),
+            opt(j.waiting()),
+            mark,
+            opt(j.response())
+        );
     }
-    if count > 0.0 {
-        println!("平均周转时间 = {:.4}", sum_turn / count);
-        println!("带权平均周转时间 = {:.4}", sum_wturn / count);
+    let metrics = Metrics::from_jobs(&jobs, m);
+    if let (Some(turn), Some(wturn)) = (metrics.avg_turnaround, metrics.avg_weighted_turnaround) {
+        println!("平均周转时间 = {:.4}", turn);
+        println!("带权平均周转时间 = {:.4}", wturn);
     }
-}
-
-// 分配到多道：返回各作业的 start/end
-// 采用非抢占式（批处理作业）调度。m 为道数（CPU 数）
-
-// 1) FCFS：按到达时间排序，作业到达后尽快分配给空闲道或等待道空
-fn schedule_fcfs(jobs: &[Job], m: usize) -> Vec<Job> {
-    let mut jobs: Vec<Job> = jobs.to_vec();
-    jobs.sort_by(|a, b| a.arrival.partial_cmp(&b.arrival).unwrap_or(Ordering::Equal));
-
-    // 每条道的可用时间
-    let mut core_free: Vec<f64> = vec![0.0; m];
-
-    for j in &mut jobs {
-        // 找到最早空闲的道及时间
-        let (idx, &free_t) = core_free.iter().enumerate().min_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal)).unwrap();
-        // 作业实际开始时间是 max(到达, 道空闲)
-        let start = j.arrival.max(free_t);
-        let end = start + j.service;
-        j.start = Some(start);
-        j.end = Some(end);
-        core_free[idx] = end; // 更新道空闲时间
+    let starved = starvation.map(|t| result::starved(&jobs, t)).unwrap_or_default();
+    if let (Some(wait), Some(resp)) = (metrics.avg_waiting, metrics.avg_response) {
+        println!("平均等待时间 = {:.4}，平均响应时间 = {:.4}", wait, resp);
     }
-    jobs
-}
-
-// 2) SJF（非抢占）：在每次调度时，从已到达且未完成的作业中选择service最小者分配到空闲道
-fn schedule_sjf(jobs: &[Job], m: usize) -> Vec<Job> {
-    let mut all: Vec<Job> = jobs.to_vec();
-    let n = all.len();
-    // 按到达时间排序用于发现新到达
-    all.sort_by(|a, b| a.arrival.partial_cmp(&b.arrival).unwrap_or(Ordering::Equal));
-
-    let mut time = 0.0f64;
-    let mut finished: Vec<Job> = Vec::with_capacity(n);
-    let mut ready: Vec<Job> = Vec::new();
-    let mut idx_next = 0; // 下一个未放入ready的作业索引
-
-    // 多道：track core_free times
-    let mut core_free: Vec<f64> = vec![0.0; m];
-
-    // 事件驱动：当有空闲道且ready不空时分配；否则推进时间到下一个到达或下一个道空闲
-    while finished.len() < n {
-        // 将已到达的作业加入 ready
-        while idx_next < all.len() && all[idx_next].arrival <= time {
-            ready.push(all[idx_next].clone());
-            idx_next += 1;
-        }
-
-        // 找空闲道
-        let mut free_idxs: Vec<usize> = core_free.iter().enumerate().filter(|(_, &t)| t <= time + 1e-9).map(|(i, _)| i).collect();
-
-        if free_idxs.is_empty() {
-            // 没有空闲道
-            if !ready.is_empty() {
-                // all busy but ready有作业，推进到最近道空闲时间
-                if let Some(&next_free) = core_free.iter().min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal)) {
-                    time = next_free;
-                    continue;
-                }
-            } else {
-                // ready 为空：推进到下一个到达或下一个空闲
-                let next_arrival = all.get(idx_next).map(|j| j.arrival);
-                let next_free = core_free.iter().cloned().min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-                match (next_arrival, next_free) {
-                    (Some(na), Some(nf)) => time = na.min(nf),
-                    (Some(na), None) => time = na,
-                    (None, Some(nf)) => time = nf,
-                    (None, None) => break,
-                }
-                continue;
-            }
-        }
-
-        // 有空闲道且ready可能为空
-        if ready.is_empty() {
-            // 若 ready 为空，推进到下一个到达
-            if let Some(j) = all.get(idx_next) {
-                time = j.arrival;
-                continue;
-            } else {
-                break;
-            }
-        }
-
-        // 对 ready 按 service 升序选择最短作业分配
-        ready.sort_by(|a, b| a.service.partial_cmp(&b.service).unwrap_or(Ordering::Equal));
-
-        for core in free_idxs {
-            if ready.is_empty() { break; }
-            let mut job = ready.remove(0);
-            let start = time.max(job.arrival);
-            let end = start + job.service;
-            job.start = Some(start);
-            job.end = Some(end);
-            finished.push(job.clone());
-            core_free[core] = end;
-        }
-
-        // time 可能保持不变，这里推进到下一个事件以避免死循环
-        // 下一个事件是最早的: 下一个到达或最早道空闲
-        let next_arrival = all.get(idx_next).map(|j| j.arrival);
-        let next_free = core_free.iter().cloned().min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-        match (next_arrival, next_free) {
-            (Some(na), Some(nf)) => time = na.min(nf),
-            (Some(na), None) => time = na,
-            (None, Some(nf)) => time = nf,
-            (None, None) => break,
-        }
+    if let (Some(max), Some(p50), Some(p90), Some(p95)) =
+        (metrics.max_turnaround, metrics.p50_turnaround, metrics.p90_turnaround, metrics.p95_turnaround)
+    {
+        println!("周转时间：最大 {:.2}，P50 {:.2}，P90 {:.2}，P95 {:.2}", max, p50, p90, p95);
     }
-
-    // finished 按 id 返回：某些作业可能尚在 ready（极少），把它们也添加并设置时间
-    // 为安全，若仍有ready未分配，逐一分配到最早空闲道
-    if !ready.is_empty() {
-        for job in ready.into_iter() {
-            let (idx, &free_t) = core_free.iter().enumerate().min_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal)).unwrap();
-            let start = job.arrival.max(free_t);
-            let end = start + job.service;
-            let mut j = job.clone();
-            j.start = Some(start);
-            j.end = Some(end);
-            core_free[idx] = end;
-            finished.push(j);
-        }
+    if let Some(throughput) = metrics.throughput {
+        let util: Vec<String> = metrics.utilization.iter().map(|u| format!("{:.1}%", u * 100.0)).collect();
+        println!("makespan = {:.2}，吞吐量 = {:.4} 个/分钟，各道利用率 {}", metrics.makespan, throughput, util.join(" "));
     }
-
-    // 结果按 id 排序返回
-    finished.sort_by(|a, b| a.id.cmp(&b.id));
-    finished
-}
-
-// 3) HRRN（响应比最高者优先）：在每次分配时选响应比最大的作业
-fn schedule_hrrn(jobs: &[Job], m: usize) -> Vec<Job> {
-    let mut all: Vec<Job> = jobs.to_vec();
-    let n = all.len();
-    all.sort_by(|a, b| a.arrival.partial_cmp(&b.arrival).unwrap_or(Ordering::Equal));
-
-    let mut time = 0.0f64;
-    let mut finished: Vec<Job> = Vec::with_capacity(n);
-    let mut ready: Vec<Job> = Vec::new();
-    let mut idx_next = 0;
-    let mut core_free: Vec<f64> = vec![0.0; m];
-
-    while finished.len() < n {
-        while idx_next < all.len() && all[idx_next].arrival <= time {
-            ready.push(all[idx_next].clone());
-            idx_next += 1;
-        }
-
-        let mut free_idxs: Vec<usize> = core_free.iter().enumerate().filter(|(_, &t)| t <= time + 1e-9).map(|(i, _)| i).collect();
-
-        if free_idxs.is_empty() {
-            if !ready.is_empty() {
-                if let Some(&next_free) = core_free.iter().min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal)) {
-                    time = next_free;
-                    continue;
-                }
-            } else {
-                let next_arrival = all.get(idx_next).map(|j| j.arrival);
-                let next_free = core_free.iter().cloned().min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-                match (next_arrival, next_free) {
-                    (Some(na), Some(nf)) => time = na.min(nf),
-                    (Some(na), None) => time = na,
-                    (None, Some(nf)) => time = nf,
-                    (None, None) => break,
-                }
-                continue;
-            }
+    // 公平性：Jain 指数越接近 1 越公平；slowdown（带权周转时间）分布的长尾反映长作业是否被拖延
+    if let (Some(jain), Some(max_wait)) = (metrics.jain_index, metrics.max_waiting) {
+        println!("公平性：Jain 指数 = {:.4}，最大等待时间 = {:.2}", jain, max_wait);
+    }
+    if let (Some(p50), Some(p90), Some(p99), Some(max)) =
+        (metrics.p50_slowdown, metrics.p90_slowdown, metrics.p99_slowdown, metrics.max_slowdown)
+    {
+        let hist = result::slowdown_histogram(&jobs);
+        let buckets: Vec<String> = SLOWDOWN_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(k, lo)| match SLOWDOWN_BUCKETS.get(k + 1) {
+                Some(hi) => format!("[{}, {}) {} 个", lo, hi, hist[k]),
+                None => format!("≥{} {} 个", lo, hist[k]),
+            })
+            .collect();
+        println!("slowdown：P50 {:.2}，P90 {:.2}，P99 {:.2}，最大 {:.2}；分布 {}", p50, p90, p99, max, buckets.join("，"));
+    }
+    if let Some(t) = starvation {
+        if starved.is_empty() {
+            println!("饥饿（等待 > {}）：无", t);
+        } else {
+            let ids: Vec<String> = starved.iter().map(|id| format!("J{}", id)).collect();
+            println!("饥饿（等待 > {}）：{}", t, ids.join(" "));
         }
-
-        if ready.is_empty() {
-            if let Some(j) = all.get(idx_next) {
-                time = j.arrival;
-                continue;
-            } else { break; }
+    }
+    // 实时调度：各作业的截止时间与延迟，错过截止时间的以 * 标出
+    if let Some(misses) = metrics.deadline_misses {
+        println!("截止时间（截止、完成、延迟）：");
+        for j in jobs.iter().filter(|j| j.deadline.is_some()) {
+            let task = j.task.map_or(String::new(), |t| format!("（周期作业 {}）", t));
+            let late = j.lateness().map_or("-".to_string(), |l| format!("{:+.2}", l));
+            let mark = if j.missed_deadline() { " *" } else { "" };
+            println!("  作业 {}{}: {}，{}，{}{}", j.id, task, at(j.deadline), at(j.end), late, mark);
         }
-
-        // 计算响应比： (waiting + service) / service = (time - arrival + service)/service
-        ready.sort_by(|a, b| {
-            let ra = (time - a.arrival + a.service) / a.service;
-            let rb = (time - b.arrival + b.service) / b.service;
-            rb.partial_cmp(&ra).unwrap_or(Ordering::Equal)
-        });
-
-        for core in free_idxs {
-            if ready.is_empty() { break; }
-            let mut job = ready.remove(0);
-            let start = time.max(job.arrival);
-            let end = start + job.service;
-            job.start = Some(start);
-            job.end = Some(end);
-            finished.push(job.clone());
-            core_free[core] = end;
+        let missed: Vec<String> = jobs.iter().filter(|j| j.missed_deadline()).map(|j| format!("J{}", j.id)).collect();
+        println!(
+            "错过截止时间 {} 个{}，最大延迟 = {}，平均拖期 = {}，最大拖期 = {}",
+            misses,
+            if missed.is_empty() { String::new() } else { format!("（{}）", missed.join(" ")) },
+            opt(metrics.max_lateness),
+            opt(metrics.avg_tardiness),
+            opt(metrics.max_tardiness)
+        );
+    }
+    // 两级调度：各作业被作业调度调入的时刻，有主存限制时还有所分得的分区
+    if jobs.iter().any(|j| j.admitted.is_some()) {
+        let with_memory = jobs.iter().any(|j| j.address.is_some());
+        println!("{}", if with_memory { "作业调度（调入时刻、分区 [起址, 末址)）：" } else { "作业调度（调入时刻）：" });
+        for j in &jobs {
+            let part = match j.address {
+                Some(a) => format!("，需要 {}，分区 [{}, {})", j.memory, a, a + j.memory),
+                None if with_memory => "，不占主存".to_string(),
+                None => String::new(),
+            };
+            println!("  作业 {}: {} 调入{}", j.id, at(j.admitted), part);
         }
-
-        let next_arrival = all.get(idx_next).map(|j| j.arrival);
-        let next_free = core_free.iter().cloned().min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-        match (next_arrival, next_free) {
-            (Some(na), Some(nf)) => time = na.min(nf),
-            (Some(na), None) => time = na,
-            (None, Some(nf)) => time = nf,
-            (None, None) => break,
+    }
+    // 被抢占过的作业另外列出各执行段
+    if jobs.iter().any(|j| j.segments.len() > 1) {
+        println!("执行段（[开始, 结束)@道）：");
+        for j in jobs.iter().filter(|j| j.segments.len() > 1) {
+            let segs: Vec<String> = j.segments.iter().map(|s| format!("[{}, {})@{}", at(Some(s.start)), at(Some(s.end)), s.channel)).collect();
+            println!("  作业 {}: {}", j.id, segs.join(" "));
         }
     }
-
-    if !ready.is_empty() {
-        for job in ready.into_iter() {
-            let (idx, &free_t) = core_free.iter().enumerate().min_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal)).unwrap();
-            let start = job.arrival.max(free_t);
-            let end = start + job.service;
-            let mut j = job.clone();
-            j.start = Some(start);
-            j.end = Some(end);
-            core_free[idx] = end;
-            finished.push(j);
+    // 多级反馈队列：各作业所在队列随时间的变化
+    if jobs.iter().any(|j| j.level_log.len() > 1) {
+        println!("所在队列（时刻→队列）：");
+        for j in &jobs {
+            let log: Vec<String> = j.level_log.iter().map(|(t, l)| format!("{}→Q{}", at(Some(*t)), l)).collect();
+            println!("  作业 {}: {}", j.id, log.join(" "));
         }
     }
-
-    finished.sort_by(|a, b| a.id.cmp(&b.id));
-    finished
 }
 
 // 用于生成样例作业流
@@ -298,36 +181,467 @@ fn sample_jobs2() -> Vec<Job> {
     ]
 }
 
-fn main() {
+// 校验作业流，不合法时打印原因并退出；origin 为作业流来源，用于错误信息
+fn stream_or_exit(jobs: Vec<Job>, origin: &str) -> JobStream {
+    match JobStream::new(jobs) {
+        Ok(stream) => stream,
+        Err(e) => {
+            eprintln!("{}: {}", origin, e);
+            process::exit(1);
+        }
+    }
+}
+
+// 读取作业流文件，失败时打印原因并退出
+// 文件用时钟时刻写到达时间时，以最早到达时刻为 0 时刻；另给出 clock 时改以 clock 为 0 时刻
+fn load_or_exit(path: &Path, clock: Option<f64>) -> JobStream {
+    match loader::load_jobs(path) {
+        Ok(mut file) => {
+            if let (Some(origin), Some(c)) = (file.origin, clock) {
+                if c > origin {
+                    eprintln!(
+                        "{}: 时钟原点 {} 晚于最早的到达时刻 {}，--clock 不能晚于第一个作业到达",
+                        path.display(),
+                        clock::format(c),
+                        clock::format(origin)
+                    );
+                    process::exit(1);
+                }
+                for j in file.jobs.iter_mut() {
+                    j.arrival += origin - c;
+                    if let Some(d) = j.deadline.as_mut() {
+                        *d += origin - c;
+                    }
+                }
+            }
+            stream_or_exit(file.jobs, &path.display().to_string()).with_clock(clock.or(file.origin))
+        }
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+// 给出文件时读取，否则使用内置样例
+fn input_or_exit(input: Option<&Path>, sample: fn() -> Vec<Job>, clock: Option<f64>) -> JobStream {
+    match input {
+        Some(path) => load_or_exit(path, clock),
+        None => stream_or_exit(sample(), "内置样例").with_clock(clock),
+    }
+}
+
+// 把周期作业展开为各个实例（见 realtime.rs）；没有周期作业时原样返回
+fn release_or_exit(tasks: &JobStream, horizon: Option<f64>) -> JobStream {
+    if tasks.jobs().iter().all(|j| j.period.is_none()) {
+        return tasks.clone();
+    }
+    let Some(horizon) = horizon.or_else(|| realtime::default_horizon(tasks.jobs())) else {
+        eprintln!("周期不全是整数，无法取超周期，请用 --horizon 给出周期作业的展开区间");
+        process::exit(1);
+    };
+    realtime::release(tasks, horizon).unwrap_or_else(|e| {
+        eprintln!("展开周期作业失败：{}", e);
+        process::exit(1);
+    })
+}
+
+// 周期作业的可调度性分析（单处理机）
+fn print_analysis(tasks: &[Job], m: usize) {
+    let tasks = realtime::tasks(tasks);
+    if tasks.is_empty() {
+        return;
+    }
+    let a = Analysis::new(&tasks);
+    let verdict = |ok: bool| if ok { "可调度" } else { "不可调度" };
+    println!("\n=== 可调度性分析（{} 个周期作业，按单处理机{}）===", tasks.len(), if m > 1 { "，多道时仅供参考" } else { "" });
+    println!("利用率 U = ΣC/T = {:.4}", a.utilization);
+    if a.implicit {
+        let ll = if a.liu_layland() { "成立，RM 可调度" } else { "不成立（充分条件，不能据此判定不可调度）" };
+        println!("RM，Liu–Layland 界：n(2^(1/n) - 1) = {:.4}，U ≤ 界 {}", a.bound, ll);
+    } else {
+        println!("RM，Liu–Layland 界：有作业截止时间不等于周期，不适用");
+    }
+    println!("RM，响应时间分析（R = C + Σ⌈R/T_j⌉C_j，周期短者优先）：{}", verdict(a.rta()));
+    for (t, r) in &a.response {
+        match r {
+            Some(r) => println!("  作业 {}: C = {}, T = {}, D = {}, R = {} ≤ D", t.id, t.service, t.period, t.deadline, r),
+            None => println!("  作业 {}: C = {}, T = {}, D = {}, R > D", t.id, t.service, t.period, t.deadline),
+        }
+    }
+    if a.implicit {
+        println!("EDF：U ≤ 1（充要条件）{}", if a.edf() { "成立，可调度" } else { "不成立，不可调度" });
+    } else {
+        let edf = if a.edf() { "成立，可调度" } else { "不成立（充分条件，不能据此判定不可调度）" };
+        println!("EDF：密度 ΣC/min(D, T) = {:.4} ≤ 1 {}", a.density, edf);
+    }
+}
+
+// 比例份额调度：分段对比各作业实得的 CPU 份额与彩票份额
+fn print_shares(windows: &[share::Window], clock: Option<f64>) {
+    let Some(overall) = windows.last() else { return };
+    let at = |t: f64| match clock {
+        Some(c) => clock::format(c + t),
+        None => format!("{:.2}", t),
+    };
+    println!("\n=== CPU 份额与彩票份额（实得 / 应得）===");
+    let header: Vec<String> = overall.shares.iter().map(|s| format!("作业 {}（{} 张）", s.job, s.tickets)).collect();
+    println!("区间\t\t{}\t最大偏差", header.join("\t"));
+    for (i, w) in windows.iter().enumerate() {
+        let span = if i + 1 == windows.len() { "全程\t".to_string() } else { format!("{}–{}", at(w.start), at(w.end)) };
+        let cells: Vec<String> = overall
+            .shares
+            .iter()
+            .map(|s| match w.shares.iter().find(|x| x.job == s.job) {
+                Some(x) => format!("{:.1}% / {:.1}%", x.got * 100.0, x.entitled * 100.0),
+                None => "-\t".to_string(),
+            })
+            .collect();
+        println!("{}\t{}\t{:.1}%", span, cells.join("\t"), w.max_deviation() * 100.0);
+    }
+}
+
+fn simulate_or_exit(jobs: &JobStream, m: usize, policy: &dyn Scheduler, opts: SimOptions) -> Vec<Job> {
+    result_or_exit(policy, scheduler::simulate(jobs, m, policy, opts))
+}
+
+// 调度并记下事件日志
+fn simulate_traced_or_exit(jobs: &JobStream, m: usize, policy: &dyn Scheduler, opts: SimOptions) -> (Vec<Job>, Trace) {
+    let mut trace = Trace::new();
+    let res = result_or_exit(policy, scheduler::simulate_traced(jobs, m, policy, opts, &mut trace));
+    (res, trace)
+}
+
+fn result_or_exit(policy: &dyn Scheduler, res: Result<Vec<Job>, ScheduleError>) -> Vec<Job> {
+    res.unwrap_or_else(|e| {
+        eprintln!("{} 调度失败：{}", policy.label(), e);
+        process::exit(1);
+    })
+}
+
+fn channels_label(m: usize) -> String {
+    match m {
+        1 => "单道".to_string(),
+        2 => "双道".to_string(),
+        _ => format!("{} 道", m),
+    }
+}
+
+// 两级调度时 m 是多道程序度，否则是道数（CPU 数）
+fn m_label(m: usize, sim: &SimOptions) -> String {
+    match sim.job_scheduler {
+        Some(_) => format!("多道程序度 {}（单 CPU）", m),
+        None => channels_label(m),
+    }
+}
+
+// 结果标题：算法 - 道数，有主存限制时附上容量与分配算法
+fn run_title(label: &str, m: usize, sim: &SimOptions) -> String {
+    match sim.memory {
+        Some(mem) if mem.capacity == u64::MAX => format!("{} - {} - 主存不限（{}）", label, m_label(m, sim), mem.fit),
+        Some(mem) => format!("{} - {} - 主存 {}（{}）", label, m_label(m, sim), mem.capacity, mem.fit),
+        None => format!("{} - {}", label, m_label(m, sim)),
+    }
+}
+
+// 原有的固定实验：三种算法 × 单道/双道，以及 FCFS 在两个作业流上的比较
+fn run_demo(stream_a: JobStream, stream_b: JobStream) {
     // 单道（m = 1）
-    let jobs = sample_jobs();
-    let res_fcfs = schedule_fcfs(&jobs, 1);
-    print_results(res_fcfs, "FCFS - 单道");
+    let jobs = stream_a.clone();
+    let res_fcfs = simulate_or_exit(&jobs, 1, &Fcfs, SimOptions::default());
+    print_results(res_fcfs, 1, "FCFS - 单道", None, stream_a.clock());
 
-    let res_sjf = schedule_sjf(&jobs, 1);
-    print_results(res_sjf, "SJF - 单道");
+    let res_sjf = simulate_or_exit(&jobs, 1, &Sjf, SimOptions::default());
+    print_results(res_sjf, 1, "SJF - 单道", None, stream_a.clock());
 
-    let res_hrrn = schedule_hrrn(&jobs, 1);
-    print_results(res_hrrn, "HRRN - 单道");
+    let res_hrrn = simulate_or_exit(&jobs, 1, &Hrrn, SimOptions::default());
+    print_results(res_hrrn, 1, "HRRN - 单道", None, stream_a.clock());
 
     // 多道（m = 2）
-    let jobs2 = sample_jobs();
-    let res_fcfs_2 = schedule_fcfs(&jobs2, 2);
-    print_results(res_fcfs_2, "FCFS - 双道");
+    let jobs2 = stream_a.clone();
+    let res_fcfs_2 = simulate_or_exit(&jobs2, 2, &Fcfs, SimOptions::default());
+    print_results(res_fcfs_2, 2, "FCFS - 双道", None, stream_a.clock());
 
-    let res_sjf_2 = schedule_sjf(&jobs2, 2);
-    print_results(res_sjf_2, "SJF - 双道");
+    let res_sjf_2 = simulate_or_exit(&jobs2, 2, &Sjf, SimOptions::default());
+    print_results(res_sjf_2, 2, "SJF - 双道", None, stream_a.clock());
 
-    let res_hrrn_2 = schedule_hrrn(&jobs2, 2);
-    print_results(res_hrrn_2, "HRRN - 双道");
+    let res_hrrn_2 = simulate_or_exit(&jobs2, 2, &Hrrn, SimOptions::default());
+    print_results(res_hrrn_2, 2, "HRRN - 双道", None, stream_a.clock());
 
     // 对不同作业流衡量同一算法
-    let stream_a = sample_jobs();
-    let stream_b = sample_jobs2();
     println!("\n=== 同一算法在不同作业流上的比较（示例） ===");
-    let a_fcfs = schedule_fcfs(&stream_a, 1);
-    let b_fcfs = schedule_fcfs(&stream_b, 1);
-    print_results(a_fcfs, "Stream A - FCFS - 单道");
-    print_results(b_fcfs, "Stream B - FCFS - 单道");
+    let a_fcfs = simulate_or_exit(&stream_a, 1, &Fcfs, SimOptions::default());
+    let b_fcfs = simulate_or_exit(&stream_b, 1, &Fcfs, SimOptions::default());
+    print_results(a_fcfs, 1, "Stream A - FCFS - 单道", None, stream_a.clock());
+    print_results(b_fcfs, 1, "Stream B - FCFS - 单道", None, stream_b.clock());
+}
+
+// 甘特图时间轴宽度（字符）
+const GANTT_WIDTH: usize = 72;
+
+// 比例份额对比的分段数
+const SHARE_WINDOWS: usize = 10;
 
+fn write_or_exit(path: &Path, text: &str) {
+    if let Err(e) = std::fs::write(path, text) {
+        eprintln!("{}: 写入失败：{}", path.display(), e);
+        process::exit(1);
+    }
+}
+
+// 文件名中只保留字母、数字与 . -，其余替换为 _
+fn file_stem(label: &str, m: usize) -> String {
+    let clean: String = label.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect();
+    format!("{}_m{}", clean.trim_matches('_'), m)
+}
+
+fn create_dir_or_exit(dir: &Path) {
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        eprintln!("{}: 创建目录失败：{}", dir.display(), e);
+        process::exit(1);
+    }
+}
+
+// 把一次运行的结果完整导出到目录：JSON、逐作业/时间线/汇总三份 CSV 以及 LaTeX 表格
+fn export_result(dir: &Path, result: &ScheduleResult) {
+    let stem = file_stem(&result.algorithm, result.m());
+    write_or_exit(&dir.join(format!("{}.tex", stem)), &latex::render_tabular(result));
+    write_or_exit(&dir.join(format!("{}.json", stem)), &result.to_json());
+    write_or_exit(&dir.join(format!("{}_jobs.csv", stem)), &result.jobs_csv(true));
+    write_or_exit(&dir.join(format!("{}_timeline.csv", stem)), &result.timeline_csv(true));
+    write_or_exit(&dir.join(format!("{}_metrics.csv", stem)), &result.metrics_csv(true));
+}
+
+fn run_single(opts: RunOpts) {
+    let tasks = input_or_exit(opts.input.as_deref(), sample_jobs, opts.clock);
+    let jobs = release_or_exit(&tasks, opts.config.horizon);
+    let policy = opts.algorithm.scheduler(&opts.config);
+    let sim = opts.config.sim;
+    let (label, cpus) = (sim.label(policy.as_ref()), sim.cpus(opts.channels));
+    let (res, trace) = if opts.trace.is_some() || opts.explain {
+        let (res, trace) = simulate_traced_or_exit(&jobs, opts.channels, policy.as_ref(), sim);
+        (res, Some(trace))
+    } else {
+        (simulate_or_exit(&jobs, opts.channels, policy.as_ref(), sim), None)
+    };
+    if let (Some(path), Some(trace)) = (&opts.trace, &trace) {
+        write_or_exit(path, &trace.to_json_lines());
+    }
+    let result = ScheduleResult::new(&label, cpus, &res).with_clock(jobs.clock()).with_degree(sim.job_scheduler.map(|_| opts.channels));
+    let title = run_title(&label, opts.channels, &sim);
+    if let Some(path) = &opts.svg {
+        write_or_exit(path, &gantt::render_svg(&res, cpus, &title));
+    }
+    if let Some(path) = &opts.tex {
+        write_or_exit(path, &latex::render_tabular(&result));
+    }
+    if let Some(dir) = &opts.export {
+        create_dir_or_exit(dir);
+        export_result(dir, &result);
+    }
+    match opts.output {
+        OutputFormat::Table => {
+            let chart = opts.gantt.then(|| gantt::render_ascii(&res, cpus, GANTT_WIDTH));
+            let shares = policy.proportional().then(|| share::windows(&res, cpus, SHARE_WINDOWS));
+            if let (true, Some(trace)) = (opts.explain, &trace) {
+                let job_policy = sim.job_scheduler.map(|alg| alg.scheduler(&SchedulerConfig::default()));
+                let text = explain::render(trace, policy.as_ref(), job_policy.as_deref(), sim.tie, jobs.clock());
+                print!("\n=== 调度过程：{} ===\n{}", title, text);
+            }
+            print_results(res, cpus, &title, opts.starvation, jobs.clock());
+            if let Some(chart) = chart {
+                print!("甘特图：\n{}", chart);
+            }
+            print_analysis(tasks.jobs(), cpus);
+            if let Some(shares) = shares {
+                print_shares(&shares, jobs.clock());
+            }
+        }
+        OutputFormat::Csv => print!("{}", result.jobs_csv(true)),
+        OutputFormat::Json => print!("{}", result.to_json()),
+        OutputFormat::Latex => print!("{}", latex::render_tabular(&result)),
+    }
+}
+
+// 使用时间片的算法按每个时间片展开
+fn expand_policies(algorithms: &[Algorithm], quanta: &[f64], config: &SchedulerConfig) -> Vec<Box<dyn Scheduler>> {
+    let mut policies = Vec::new();
+    for &alg in algorithms {
+        if alg.uses_quantum() {
+            for &q in quanta {
+                policies.push(alg.scheduler(&SchedulerConfig { quantum: q, ..config.clone() }));
+            }
+        } else {
+            policies.push(alg.scheduler(config));
+        }
+    }
+    policies
+}
+
+fn run_compare(opts: CompareOpts) {
+    let tasks = input_or_exit(opts.input.as_deref(), sample_jobs, opts.clock);
+    let jobs = release_or_exit(&tasks, opts.config.horizon);
+    let policies = expand_policies(&opts.algorithms, &opts.quanta, &opts.config);
+    for dir in [&opts.svg_dir, &opts.tex_dir, &opts.export].into_iter().flatten() {
+        create_dir_or_exit(dir);
+    }
+    let mut results = Vec::new();
+    for &m in &opts.channels {
+        for policy in &policies {
+            let sim = opts.config.sim;
+            let (label, cpus) = (sim.label(policy.as_ref()), sim.cpus(m));
+            let res = simulate_or_exit(&jobs, m, policy.as_ref(), sim);
+            let result = ScheduleResult::new(&label, cpus, &res).with_clock(jobs.clock()).with_degree(sim.job_scheduler.map(|_| m));
+            let title = run_title(&label, m, &sim);
+            if let Some(dir) = &opts.svg_dir {
+                let path = dir.join(format!("{}.svg", file_stem(&label, m)));
+                write_or_exit(&path, &gantt::render_svg(&res, cpus, &title));
+            }
+            if let Some(dir) = &opts.tex_dir {
+                let path = dir.join(format!("{}.tex", file_stem(&label, m)));
+                write_or_exit(&path, &latex::render_tabular(&result));
+            }
+            if let Some(dir) = &opts.export {
+                export_result(dir, &result);
+            }
+            if opts.output == OutputFormat::Table {
+                print_results(res.clone(), cpus, &title, opts.starvation, jobs.clock());
+                if opts.gantt {
+                    print!("甘特图：\n{}", gantt::render_ascii(&res, cpus, GANTT_WIDTH));
+                }
+                if policy.proportional() {
+                    print_shares(&share::windows(&res, cpus, SHARE_WINDOWS), jobs.clock());
+                }
+            }
+            results.push(result);
+        }
+    }
+    match opts.output {
+        OutputFormat::Table => {
+            print_analysis(tasks.jobs(), opts.channels.iter().copied().max().unwrap_or(1));
+            let with_deadline = results.first().is_some_and(|r| r.metrics.deadline_misses.is_some());
+            println!("\n=== 算法比较 ===");
+            println!("alg\tm\tavg_turn\tavg_wturn\tavg_wait\tavg_resp\tmax_turn\tutil\tjain\tmax_slow{}", if with_deadline { "\tmiss\tmax_late" } else { "" });
+            for r in &results {
+                let m = &r.metrics;
+                let t = m.avg_turnaround.unwrap_or(f64::NAN);
+                let w = m.avg_weighted_turnaround.unwrap_or(f64::NAN);
+                let wait = m.avg_waiting.unwrap_or(f64::NAN);
+                let resp = m.avg_response.unwrap_or(f64::NAN);
+                let max = m.max_turnaround.unwrap_or(f64::NAN);
+                let util = m.avg_utilization().unwrap_or(f64::NAN) * 100.0;
+                let jain = m.jain_index.unwrap_or(f64::NAN);
+                let slow = m.max_slowdown.unwrap_or(f64::NAN);
+                let deadline = match m.deadline_misses {
+                    Some(misses) => format!("\t{}\t{:.2}", misses, m.max_lateness.unwrap_or(f64::NAN)),
+                    None => String::new(),
+                };
+                println!(
+                    "{}\t{}\t{:.4}\t\t{:.4}\t\t{:.4}\t\t{:.4}\t\t{:.2}\t\t{:.1}%\t{:.4}\t{:.2}{}",
+                    r.algorithm, r.m(), t, w, wait, resp, max, util, jain, slow, deadline
+                );
+            }
+        }
+        OutputFormat::Csv => {
+            for (i, r) in results.iter().enumerate() {
+                print!("{}", r.metrics_csv(i == 0));
+            }
+        }
+        OutputFormat::Json => {
+            print!("{}", json::Value::Arr(results.iter().map(ScheduleResult::to_json_value).collect()).to_pretty());
+        }
+        OutputFormat::Latex => {
+            for r in &results {
+                print!("{}", latex::render_tabular(r));
+            }
+        }
+    }
+}
+
+// 均值 ± 置信区间半宽；只有一个作业流时没有区间
+fn fmt_summary(s: Option<Summary>) -> String {
+    match s {
+        Some(Summary { mean, ci95: Some(ci), .. }) => format!("{:.4} ± {:.4}", mean, ci),
+        Some(Summary { mean, .. }) => format!("{:.4}", mean),
+        None => "-".to_string(),
+    }
+}
+
+fn run_experiment(opts: ExperimentOpts) {
+    let policies = expand_policies(&opts.algorithms, &opts.quanta, &opts.config);
+    let exp = experiment::run(&policies, &opts.channels, &opts.workload, opts.workloads, opts.config.sim).unwrap_or_else(|e| {
+        eprintln!("实验失败：{}", e);
+        process::exit(1);
+    });
+    match opts.output {
+        OutputFormat::Table => {
+            println!("\n=== 批量实验 ===");
+            println!("{}", exp.describe());
+            println!("{:<24}{:>3}  {:>20}{:>10}  {:>20}{:>10}{:>6}", "alg", "m", "avg_turn (95% CI)", "std", "avg_wturn (95% CI)", "std", "wins");
+            for r in &exp.rows {
+                let std = |s: Option<Summary>| s.and_then(|s| s.std_dev).map_or("-".to_string(), |v| format!("{:.4}", v));
+                println!(
+                    "{:<24}{:>3}  {:>20}{:>10}  {:>20}{:>10}{:>6}",
+                    r.algorithm,
+                    r.channels,
+                    fmt_summary(r.turnaround),
+                    std(r.turnaround),
+                    fmt_summary(r.weighted_turnaround),
+                    std(r.weighted_turnaround),
+                    r.wins
+                );
+            }
+            // 每个道数下按平均周转时间的均值排序，给出相对最优者的比值
+            println!("\n=== 算法比较（按平均周转时间均值排序） ===");
+            for &m in &opts.channels {
+                let mut rows: Vec<_> = exp.rows.iter().filter(|r| r.channels == m && r.turnaround.is_some()).collect();
+                rows.sort_by(|a, b| a.turnaround.unwrap().mean.total_cmp(&b.turnaround.unwrap().mean));
+                let Some(best) = rows.first().and_then(|r| r.turnaround).map(|s| s.mean) else { continue };
+                println!("{}：", m_label(m, &opts.config.sim));
+                for (rank, r) in rows.iter().enumerate() {
+                    let mean = r.turnaround.unwrap().mean;
+                    println!("  {}. {:<24}{:.4}\t×{:.3}\t最优 {}/{} 次", rank + 1, r.algorithm, mean, mean / best, r.wins, exp.workloads);
+                }
+            }
+        }
+        OutputFormat::Csv => print!("{}", exp.to_csv()),
+        OutputFormat::Json => print!("{}", exp.to_json()),
+        OutputFormat::Latex => print!("{}", latex::render_experiment(&exp)),
+    }
+}
+
+fn run_generate(opts: GenerateOpts) {
+    let jobs = match opts.workload {
+        Workload::Sample(Sample::A) => sample_jobs(),
+        Workload::Sample(Sample::B) => sample_jobs2(),
+        Workload::Random(spec) => generator::generate(&spec),
+    };
+    let text = loader::format_jobs(&jobs, opts.format);
+    match opts.output {
+        Some(path) => write_or_exit(&path, &text),
+        None => print!("{}", text),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match cli::parse_args(&args) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("错误：{}\n\n{}", e, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    match command {
+        Command::Demo { stream_a, stream_b } => {
+            let a = input_or_exit(stream_a.as_deref(), sample_jobs, None);
+            let b = input_or_exit(stream_b.as_deref(), sample_jobs2, None);
+            run_demo(a, b);
+        }
+        Command::Run(opts) => run_single(opts),
+        Command::Compare(opts) => run_compare(opts),
+        Command::Experiment(opts) => run_experiment(opts),
+        Command::Generate(opts) => run_generate(opts),
+        Command::Help => print!("{}", cli::USAGE),
+    }
 }
@@ -0,0 +1,167 @@
+// 主存的可变分区分配：作业调度时按首次/最佳/最坏适应为作业划出一个分区，作业完成时回收并与相邻空闲区合并
+
+use std::fmt;
+
+// 选择空闲区的算法
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fit {
+    First, // 地址最低的、足够大的空闲区（缺省）
+    Best,  // 足够大的空闲区中最小者
+    Worst, // 最大的空闲区
+}
+
+impl Fit {
+    pub fn parse(name: &str) -> Option<Fit> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "first" => Some(Fit::First),
+            "best" => Some(Fit::Best),
+            "worst" => Some(Fit::Worst),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Fit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Fit::First => "first-fit",
+            Fit::Best => "best-fit",
+            Fit::Worst => "worst-fit",
+        })
+    }
+}
+
+// 主存模型：总容量（与作业的 memory 同一单位，如 KB）与分配算法
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryModel {
+    pub capacity: u64,
+    pub fit: Fit,
+}
+
+// 一次调度过程中的主存状态：按地址排列的空闲区 (起始地址, 长度)
+pub struct Memory {
+    fit: Fit,
+    holes: Vec<(u64, u64)>,
+}
+
+impl Memory {
+    pub fn new(model: MemoryModel) -> Self {
+        let holes = if model.capacity > 0 { vec![(0, model.capacity)] } else { Vec::new() };
+        Memory { fit: model.fit, holes }
+    }
+
+    // 是否有足够大的空闲区
+    pub fn fits(&self, size: u64) -> bool {
+        self.holes.iter().any(|&(_, len)| len >= size)
+    }
+
+    // 划出 size 大小的分区，返回起始地址；没有足够大的空闲区时返回 None。
+    // 分区取自空闲区的低地址端，几个空闲区同样合适时取地址低者
+    pub fn allocate(&mut self, size: u64) -> Option<u64> {
+        let fits = self.holes.iter().enumerate().filter(|(_, &(_, len))| len >= size);
+        let (i, _) = match self.fit {
+            Fit::First => fits.min_by_key(|&(i, _)| i),
+            Fit::Best => fits.min_by_key(|&(i, &(_, len))| (len, i)),
+            Fit::Worst => fits.min_by_key(|&(i, &(_, len))| (u64::MAX - len, i)),
+        }?;
+        let (start, len) = self.holes[i];
+        if len == size {
+            self.holes.remove(i);
+        } else {
+            self.holes[i] = (start + size, len - size);
+        }
+        Some(start)
+    }
+
+    // 回收分区，与前后相邻的空闲区合并
+    pub fn free(&mut self, start: u64, size: u64) {
+        if size == 0 {
+            return;
+        }
+        let i = self.holes.partition_point(|&(s, _)| s < start);
+        self.holes.insert(i, (start, size));
+        if i + 1 < self.holes.len() && start + size == self.holes[i + 1].0 {
+            self.holes[i].1 += self.holes.remove(i + 1).1;
+        }
+        if i > 0 && self.holes[i - 1].0 + self.holes[i - 1].1 == start {
+            self.holes[i - 1].1 += self.holes.remove(i).1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::{Job, JobStream};
+    use crate::scheduler::{simulate, Fcfs, ScheduleError, SimOptions};
+
+    // 容量 95，依次划出 10、20、10、30、10 后回收第 2、4 块：
+    // 空闲区 (10, 20)、(40, 30)、(80, 15)
+    fn fragmented(fit: Fit) -> Memory {
+        let mut mem = Memory::new(MemoryModel { capacity: 95, fit });
+        let starts: Vec<u64> = [10, 20, 10, 30, 10].iter().map(|&size| mem.allocate(size).unwrap()).collect();
+        assert_eq!(starts, [0, 10, 30, 40, 70]);
+        mem.free(10, 20);
+        mem.free(40, 30);
+        assert_eq!(mem.holes, [(10, 20), (40, 30), (80, 15)]);
+        mem
+    }
+
+    #[test]
+    fn placement_by_fit() {
+        // 大小 15：首次适应取地址最低的 10，最佳适应取正好 15 的 80，最坏适应取最大的 40
+        for (fit, at, holes) in [
+            (Fit::First, 10, vec![(25, 5), (40, 30), (80, 15)]),
+            (Fit::Best, 80, vec![(10, 20), (40, 30)]),
+            (Fit::Worst, 40, vec![(10, 20), (55, 15), (80, 15)]),
+        ] {
+            let mut mem = fragmented(fit);
+            assert_eq!(mem.allocate(15), Some(at), "{}", fit);
+            assert_eq!(mem.holes, holes, "{}", fit);
+        }
+        // 同样合适的空闲区取地址低者
+        let mut mem = fragmented(Fit::Best);
+        mem.free(0, 10);
+        assert_eq!(mem.holes, [(0, 30), (40, 30), (80, 15)]);
+        assert_eq!(mem.allocate(30), Some(0));
+        let mut mem = Memory::new(MemoryModel { capacity: 100, fit: Fit::Worst });
+        assert_eq!((mem.allocate(30), mem.allocate(40)), (Some(0), Some(30)));
+        mem.free(0, 30);
+        assert_eq!(mem.holes, [(0, 30), (70, 30)]);
+        assert_eq!(mem.allocate(5), Some(0));
+    }
+
+    #[test]
+    fn free_merges_neighbouring_holes() {
+        let mut mem = fragmented(Fit::First);
+        // 与前后两个空闲区都相邻
+        mem.free(30, 10);
+        assert_eq!(mem.holes, [(10, 60), (80, 15)]);
+        // 只与后一个相邻、只与前一个相邻
+        mem.free(0, 10);
+        assert_eq!(mem.holes, [(0, 70), (80, 15)]);
+        mem.free(70, 10);
+        assert_eq!(mem.holes, [(0, 95)]);
+        mem.free(0, 0);
+        assert_eq!(mem.holes, [(0, 95)]);
+        assert_eq!(mem.allocate(95), Some(0));
+        assert!(mem.holes.is_empty());
+    }
+
+    #[test]
+    fn jobs_larger_than_memory() {
+        // 总空闲 65 足够，但没有一个空闲区放得下 31
+        let mut mem = fragmented(Fit::First);
+        assert!(mem.fits(30) && !mem.fits(31));
+        assert_eq!(mem.allocate(31), None);
+        assert_eq!(mem.holes, [(10, 20), (40, 30), (80, 15)]);
+        // 超过总容量的作业永远无法调入，调度前即报错
+        let model = MemoryModel { capacity: 95, fit: Fit::First };
+        let stream = JobStream::new(vec![Job::new(1, 0.0, 1.0).with_memory(50), Job::new(2, 0.0, 1.0).with_memory(96)]).unwrap();
+        let opts = SimOptions { memory: Some(model), ..SimOptions::default() };
+        assert!(matches!(
+            simulate(&stream, 1, &Fcfs, opts),
+            Err(ScheduleError::MemoryExceeded { id: 2, memory: 96, capacity: 95 })
+        ));
+    }
+}
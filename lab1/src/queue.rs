@@ -0,0 +1,281 @@
+// 就绪队列（两级调度时也用作后备队列）
+//
+// 策略给出与时间无关的排序关键字（Scheduler::key）且取舍不依赖随机数时，用二叉堆按
+// (关键字, 取舍键, 进入队列的序号) 维护，每次取出 O(log n)；否则按进入顺序存放在数组中，
+// 由 Scheduler::select 在整个队列上挑选（如 HRRN 的响应比随时间变化，只能逐个比较）
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use crate::job::Job;
+use crate::scheduler::{Scheduler, TieBreak, TieBreaker};
+
+struct Entry {
+    key: f64,
+    tie: f64, // 关键字相等时的取舍键：id 或到达时间；按进入顺序取舍时为 0
+    seq: u64,
+    job: Job,
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.total_cmp(&other.key).then(self.tie.total_cmp(&other.tie)).then(self.seq.cmp(&other.seq))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+// 堆中的排序键；-0.0 与 0.0 视为相等，与 select 中的 == 比较一致
+fn entry(policy: &dyn Scheduler, rule: TieBreak, job: Job, seq: u64) -> Entry {
+    let key = policy.key(&job).unwrap_or(0.0) + 0.0;
+    let tie = if policy.fifo_ties() {
+        0.0
+    } else {
+        match rule {
+            TieBreak::LowerId => job.id as f64,
+            TieBreak::EarlierArrival => job.arrival,
+            TieBreak::Fifo | TieBreak::Random(_) => 0.0,
+        }
+    };
+    Entry { key, tie, seq, job }
+}
+
+enum Items {
+    Heap(BinaryHeap<Reverse<Entry>>),
+    List(Vec<Job>),
+}
+
+pub struct ReadyQueue<'a> {
+    policy: &'a dyn Scheduler,
+    rule: TieBreak,
+    seq: u64,
+    global: f64, // 最近一次取出的作业的关键字，新作业据此确定起点（见 Scheduler::on_join）
+    items: Items,
+}
+
+impl<'a> ReadyQueue<'a> {
+    pub fn new(policy: &'a dyn Scheduler, rule: TieBreak) -> Self {
+        let items = if policy.keyed() && (policy.fifo_ties() || !matches!(rule, TieBreak::Random(_))) {
+            Items::Heap(BinaryHeap::new())
+        } else {
+            Items::List(Vec::new())
+        };
+        ReadyQueue { policy, rule, seq: 0, global: 0.0, items }
+    }
+
+    // 挑选所用的策略
+    pub fn policy(&self) -> &'a dyn Scheduler {
+        self.policy
+    }
+
+    // 是否按关键字用堆维护
+    pub fn keyed(&self) -> bool {
+        matches!(self.items, Items::Heap(_))
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.items {
+            Items::Heap(heap) => heap.len(),
+            Items::List(list) => list.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 作业进入队列：记下进入时刻，老化提升清零（老化只计本次在队列中的等待）
+    pub fn push(&mut self, mut job: Job, now: f64) {
+        job.enqueued = now;
+        job.aged = 0;
+        self.seq += 1;
+        match &mut self.items {
+            Items::Heap(heap) => heap.push(Reverse(entry(self.policy, self.rule, job, self.seq))),
+            Items::List(list) => list.push(job),
+        }
+    }
+
+    // 新作业（到达或被调入）进入队列：先由策略记下进入时刻等状态（Scheduler::on_arrive、Scheduler::on_join）
+    pub fn enter(&mut self, mut job: Job, now: f64) {
+        self.policy.on_arrive(&mut job, now);
+        self.policy.on_join(&mut job, self.global);
+        self.push(job, now);
+    }
+
+    // 取出策略挑中的作业
+    pub fn pop(&mut self, now: f64, tie: &mut TieBreaker) -> Option<Job> {
+        let job = match &mut self.items {
+            Items::Heap(heap) => heap.pop().map(|Reverse(e)| e.job),
+            Items::List(list) if list.is_empty() => None,
+            Items::List(list) => {
+                let i = self.policy.select(list, now, tie);
+                Some(list.remove(i))
+            }
+        };
+        self.taken(job)
+    }
+
+    fn taken(&mut self, job: Option<Job>) -> Option<Job> {
+        if let Some(key) = job.as_ref().and_then(|j| self.policy.key(j)) {
+            self.global = key;
+        }
+        job
+    }
+
+    // 只在满足 accept 的作业中挑选并取出（如放得下主存的作业）
+    pub fn pop_where(&mut self, now: f64, tie: &mut TieBreaker, accept: impl Fn(&Job) -> bool) -> Option<Job> {
+        let job = match &mut self.items {
+            Items::Heap(heap) => {
+                let mut rejected = Vec::new();
+                let mut found = None;
+                while let Some(Reverse(e)) = heap.pop() {
+                    if accept(&e.job) {
+                        found = Some(e.job);
+                        break;
+                    }
+                    rejected.push(Reverse(e));
+                }
+                heap.extend(rejected);
+                found
+            }
+            Items::List(list) => {
+                let fitting: Vec<usize> = (0..list.len()).filter(|&i| accept(&list[i])).collect();
+                if fitting.is_empty() {
+                    return None;
+                }
+                let candidates: Vec<Job> = fitting.iter().map(|&i| list[i].clone()).collect();
+                Some(list.remove(fitting[self.policy.select(&candidates, now, tie)]))
+            }
+        };
+        self.taken(job)
+    }
+
+    // 按挑选顺序依次访问前若干个作业的关键字（visit 返回 false 时停止），不改变队列。仅用于堆
+    pub fn scan(&mut self, mut visit: impl FnMut(usize, f64) -> bool) {
+        let Items::Heap(heap) = &mut self.items else { return };
+        let mut seen = Vec::new();
+        while let Some(Reverse(e)) = heap.pop() {
+            let more = visit(seen.len(), e.key);
+            seen.push(Reverse(e));
+            if !more {
+                break;
+            }
+        }
+        heap.extend(seen);
+    }
+
+    // 按进入顺序排列的作业；堆中的作业不按顺序，只用于数组
+    pub fn jobs(&self) -> &[Job] {
+        match &self.items {
+            Items::Heap(_) => &[],
+            Items::List(list) => list,
+        }
+    }
+
+    // 队列中的作业：堆按挑选顺序，数组按进入顺序（用于事件日志）
+    pub fn queued(&self) -> Vec<&Job> {
+        match &self.items {
+            Items::Heap(heap) => {
+                let mut entries: Vec<&Entry> = heap.iter().map(|Reverse(e)| e).collect();
+                entries.sort();
+                entries.into_iter().map(|e| &e.job).collect()
+            }
+            Items::List(list) => list.iter().collect(),
+        }
+    }
+
+    pub fn ids(&self) -> Vec<usize> {
+        self.queued().into_iter().map(|j| j.id).collect()
+    }
+
+    // 修改队列中的每个作业（如定时事件），之后按新的关键字重建堆
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(&mut Job)) {
+        match &mut self.items {
+            Items::Heap(heap) => {
+                let mut entries = std::mem::take(heap).into_vec();
+                for Reverse(e) in entries.iter_mut() {
+                    f(&mut e.job);
+                }
+                let rebuilt = entries.into_iter().map(|Reverse(e)| Reverse(entry(self.policy, self.rule, e.job, e.seq)));
+                *heap = rebuilt.collect();
+            }
+            Items::List(list) => list.iter_mut().for_each(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{ChannelTie, Hrrn, Sjf};
+
+    fn jobs() -> Vec<Job> {
+        [(1, 0.0, 5.0), (2, 1.0, 3.0), (3, 2.0, 5.0), (4, 3.0, 3.0), (5, 4.0, 1.0)].iter().map(|&(id, a, s)| Job::new(id, a, s)).collect()
+    }
+
+    fn drain(queue: &mut ReadyQueue, now: f64, tie: &mut TieBreaker) -> Vec<usize> {
+        std::iter::from_fn(|| queue.pop(now, tie).map(|j| j.id)).collect()
+    }
+
+    #[test]
+    fn keyed_policy_uses_heap_in_key_then_fifo_order() {
+        let mut tie = TieBreaker::new(TieBreak::Fifo, ChannelTie::Lowest);
+        let mut queue = ReadyQueue::new(&Sjf, TieBreak::Fifo);
+        assert!(queue.keyed());
+        for j in jobs() {
+            queue.enter(j, 4.0);
+        }
+        assert_eq!(queue.ids(), vec![5, 2, 4, 1, 3]);
+        assert_eq!(drain(&mut queue, 4.0, &mut tie), vec![5, 2, 4, 1, 3]);
+    }
+
+    #[test]
+    fn heap_applies_tie_rule() {
+        let mut tie = TieBreaker::new(TieBreak::LowerId, ChannelTie::Lowest);
+        let mut queue = ReadyQueue::new(&Sjf, TieBreak::LowerId);
+        for j in jobs().into_iter().rev() {
+            queue.enter(j, 4.0);
+        }
+        assert_eq!(drain(&mut queue, 4.0, &mut tie), vec![5, 2, 4, 1, 3]);
+    }
+
+    #[test]
+    fn random_ties_and_unkeyed_policies_use_list() {
+        assert!(!ReadyQueue::new(&Sjf, TieBreak::Random(1)).keyed());
+        let mut tie = TieBreaker::new(TieBreak::Fifo, ChannelTie::Lowest);
+        let mut queue = ReadyQueue::new(&Hrrn, TieBreak::Fifo);
+        assert!(!queue.keyed());
+        for j in jobs() {
+            queue.enter(j, 4.0);
+        }
+        // 响应比：1 → 9/5，2 → 6/3，3 → 7/5，4 → 4/3，5 → 1
+        assert_eq!(queue.ids(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(drain(&mut queue, 4.0, &mut tie), vec![2, 1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn pop_where_skips_rejected_jobs() {
+        let mut tie = TieBreaker::new(TieBreak::Fifo, ChannelTie::Lowest);
+        for policy in [&Sjf as &dyn Scheduler, &Hrrn] {
+            let mut queue = ReadyQueue::new(policy, TieBreak::Fifo);
+            for j in jobs() {
+                queue.enter(j, 4.0);
+            }
+            let picked = queue.pop_where(4.0, &mut tie, |j| j.id % 2 == 1).map(|j| j.id);
+            assert_eq!(picked, Some(if queue.keyed() { 5 } else { 1 }), "{}", policy.name());
+            assert_eq!(queue.len(), 4);
+        }
+    }
+}
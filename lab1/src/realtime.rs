@@ -0,0 +1,237 @@
+// 实时调度：周期作业的展开与可调度性分析
+//
+// 带 period 的作业表示一个周期作业（任务）：从 arrival 起每隔 period 释放一个实例，运行时间均为 service，
+// 相对截止时间 D 为 deadline - arrival（未给出截止时间时 D = period）。调度前用 release 展开到给定时刻，
+// 各实例作为普通作业交给引擎；可调度性分析只看周期作业本身，按单处理机计算
+
+use crate::job::{Job, JobStream};
+use crate::scheduler::ScheduleError;
+
+// 周期作业的参数：运行时间 C、周期 T、相对截止时间 D
+#[derive(Clone, Copy, Debug)]
+pub struct Task {
+    pub id: usize,
+    pub service: f64,
+    pub period: f64,
+    pub deadline: f64,
+}
+
+pub fn tasks(jobs: &[Job]) -> Vec<Task> {
+    jobs.iter()
+        .filter_map(|j| {
+            let period = j.period?;
+            let deadline = j.deadline.map_or(period, |d| d - j.arrival);
+            Some(Task { id: j.id, service: j.service, period, deadline })
+        })
+        .collect()
+}
+
+// 缺省的展开区间：最晚的首次释放时刻加上超周期（各周期的最小公倍数）；
+// 周期不全是整数或没有周期作业时为 None
+pub fn default_horizon(jobs: &[Job]) -> Option<f64> {
+    let mut lcm: u64 = 1;
+    let mut offset: f64 = 0.0;
+    let mut any = false;
+    for j in jobs {
+        let Some(period) = j.period else { continue };
+        if period.fract() != 0.0 || period > 1e9 {
+            return None;
+        }
+        let p = period as u64;
+        lcm = lcm.checked_mul(p / gcd(lcm, p))?;
+        offset = offset.max(j.arrival);
+        any = true;
+    }
+    (any && lcm <= 1 << 40).then_some(offset + lcm as f64)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// 展开后实例总数的上限：周期两两互素时超周期极大（如 3, 5, 7, …, 29 的超周期约 32 亿），
+// 不加限制会一直展开到内存耗尽
+pub const MAX_INSTANCES: usize = 100_000;
+
+// 把周期作业展开为在 horizon 之前释放的各个实例：第一个实例沿用原 id，之后的实例按释放先后
+// 取最大 id 之后的编号，task 记为原 id。非周期作业原样保留
+pub fn release(stream: &JobStream, horizon: f64) -> Result<JobStream, ScheduleError> {
+    let jobs = stream.jobs();
+    // 每个周期作业至少释放一个实例，之后在 horizon 之前每隔 period 释放一个
+    let count: f64 = jobs
+        .iter()
+        .map(|j| match j.period {
+            Some(period) => ((horizon - j.arrival) / period).ceil().max(1.0),
+            None => 1.0,
+        })
+        .sum();
+    if count > MAX_INSTANCES as f64 {
+        return Err(ScheduleError::TooManyInstances { count, horizon });
+    }
+    let mut next_id = jobs.iter().map(|j| j.id).max().map_or(0, |id| id + 1);
+    let mut out = Vec::with_capacity(jobs.len());
+    let mut later = Vec::new(); // (释放时刻, 周期作业 id, 实例)
+    for j in jobs {
+        let Some(period) = j.period else {
+            out.push(j.clone());
+            continue;
+        };
+        let relative = j.deadline.map_or(period, |d| d - j.arrival);
+        let mut k = 0;
+        loop {
+            let at = j.arrival + k as f64 * period;
+            if k > 0 && at >= horizon {
+                break;
+            }
+            let mut instance = j.clone();
+            instance.arrival = at;
+            instance.deadline = Some(at + relative);
+            instance.task = Some(j.id);
+            if k == 0 {
+                out.push(instance);
+            } else {
+                later.push((at, j.id, instance));
+            }
+            k += 1;
+        }
+    }
+    later.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    for (_, _, mut instance) in later {
+        instance.id = next_id;
+        next_id += 1;
+        out.push(instance);
+    }
+    Ok(JobStream::new(out)?.with_clock(stream.clock()))
+}
+
+// 单处理机上的可调度性分析
+#[derive(Clone, Debug)]
+pub struct Analysis {
+    pub utilization: f64, // U = Σ C/T
+    pub bound: f64,       // Liu–Layland 界 n(2^(1/n) - 1)
+    pub density: f64,     // Σ C/min(D, T)，D < T 时 EDF 的充分条件用它代替 U
+    pub implicit: bool,   // 所有 D = T（Liu–Layland 界与 EDF 的 U ≤ 1 都以此为前提）
+    pub response: Vec<(Task, Option<f64>)>, // RM 优先级（周期短者优先）下各任务的最坏响应时间，超过 D 时为 None
+}
+
+impl Analysis {
+    pub fn new(tasks: &[Task]) -> Self {
+        let n = tasks.len() as f64;
+        let mut by_rate = tasks.to_vec();
+        by_rate.sort_by(|a, b| a.period.total_cmp(&b.period).then(a.id.cmp(&b.id)));
+        let response = (0..by_rate.len()).map(|i| (by_rate[i], response_time(&by_rate[..i], &by_rate[i]))).collect();
+        Analysis {
+            utilization: tasks.iter().map(|t| t.service / t.period).sum(),
+            bound: n * (2f64.powf(1.0 / n) - 1.0),
+            density: tasks.iter().map(|t| t.service / t.deadline.min(t.period)).sum(),
+            implicit: tasks.iter().all(|t| (t.deadline - t.period).abs() <= 1e-9),
+            response,
+        }
+    }
+
+    // RM 的 Liu–Layland 测试：U ≤ n(2^(1/n) - 1) 时可调度（充分条件）
+    pub fn liu_layland(&self) -> bool {
+        self.implicit && self.utilization <= self.bound + 1e-9
+    }
+
+    // RM 的响应时间分析：每个任务的最坏响应时间都不超过 D 时可调度（充要条件）
+    pub fn rta(&self) -> bool {
+        self.response.iter().all(|(_, r)| r.is_some())
+    }
+
+    // EDF：D = T 时 U ≤ 1 为充要条件，否则用密度 Σ C/min(D, T) ≤ 1（充分条件）
+    pub fn edf(&self) -> bool {
+        self.density <= 1.0 + 1e-9
+    }
+}
+
+// 响应时间迭代 R = C + Σ ⌈R / T_j⌉ C_j（j 为优先级更高的任务），收敛即为最坏响应时间，超过 D 则不可调度
+fn response_time(higher: &[Task], task: &Task) -> Option<f64> {
+    let mut r = task.service;
+    loop {
+        let next = task.service + higher.iter().map(|h| (r / h.period - 1e-9).ceil() * h.service).sum::<f64>();
+        if next > task.deadline + 1e-9 {
+            return None;
+        }
+        if (next - r).abs() <= 1e-9 {
+            return Some(next);
+        }
+        r = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: usize, service: f64, period: f64, deadline: f64) -> Task {
+        Task { id, service, period, deadline }
+    }
+
+    fn responses(a: &Analysis) -> Vec<(usize, Option<f64>)> {
+        a.response.iter().map(|(t, r)| (t.id, *r)).collect()
+    }
+
+    #[test]
+    fn liu_layland_bound() {
+        let light = Analysis::new(&[task(1, 1.0, 4.0, 4.0), task(2, 1.0, 5.0, 5.0)]);
+        assert!((light.bound - 2.0 * (2f64.sqrt() - 1.0)).abs() < 1e-12);
+        assert!((light.utilization - 0.45).abs() < 1e-12);
+        assert!(light.liu_layland() && light.rta() && light.edf());
+        // U = 0.8333 超过 3 个任务的界 0.7798，但响应时间分析仍可调度
+        let heavy = Analysis::new(&[task(3, 3.0, 12.0, 12.0), task(1, 1.0, 4.0, 4.0), task(2, 2.0, 6.0, 6.0)]);
+        assert!(!heavy.liu_layland() && heavy.rta() && heavy.edf());
+        // 截止时间短于周期时不适用 Liu–Layland 界，EDF 改看密度
+        let constrained = Analysis::new(&[task(1, 1.0, 4.0, 2.0), task(2, 1.0, 5.0, 5.0)]);
+        assert!(!constrained.implicit && !constrained.liu_layland());
+        assert!((constrained.density - 0.7).abs() < 1e-12 && constrained.edf());
+    }
+
+    #[test]
+    fn response_time_analysis() {
+        // 按周期排成 RM 优先级：R1 = 1，R2 = 2 + ⌈3/4⌉·1 = 3，R3 迭代 3 → 6 → 7 → 9 → 10 → 10
+        let a = Analysis::new(&[task(3, 3.0, 12.0, 12.0), task(1, 1.0, 4.0, 4.0), task(2, 2.0, 6.0, 6.0)]);
+        assert_eq!(responses(&a), [(1, Some(1.0)), (2, Some(3.0)), (3, Some(10.0))]);
+        // U = 1：RM 下任务 2 的响应时间 3 → 5 → 7 超过 D = 6，EDF 仍可调度
+        let full = Analysis::new(&[task(1, 2.0, 4.0, 4.0), task(2, 3.0, 6.0, 6.0)]);
+        assert_eq!(responses(&full), [(1, Some(2.0)), (2, None)]);
+        assert!(!full.rta() && full.edf());
+    }
+
+    #[test]
+    fn release_numbers_instances_by_release_time() {
+        let stream = JobStream::new(vec![
+            Job::new(1, 0.0, 1.0).with_period(Some(4.0)),
+            Job::new(2, 1.0, 1.0).with_period(Some(3.0)).with_deadline(Some(3.0)),
+            Job::new(3, 5.0, 2.0),
+        ])
+        .unwrap();
+        let out = release(&stream, 8.0).unwrap();
+        let got: Vec<_> = out.jobs().iter().map(|j| (j.id, j.arrival, j.deadline, j.task)).collect();
+        assert_eq!(
+            got,
+            [
+                (1, 0.0, Some(4.0), Some(1)),
+                (2, 1.0, Some(3.0), Some(2)),
+                (3, 5.0, None, None),
+                (4, 4.0, Some(8.0), Some(1)),
+                (5, 4.0, Some(6.0), Some(2)),
+                (6, 7.0, Some(9.0), Some(2)),
+            ]
+        );
+        // 超周期：最晚的首次释放 1 加上 lcm(4, 3) = 12
+        assert_eq!(default_horizon(stream.jobs()), Some(13.0));
+        assert_eq!(default_horizon(&[Job::new(1, 0.0, 1.0).with_period(Some(2.5))]), None);
+    }
+
+    #[test]
+    fn release_caps_instance_count() {
+        let jobs: Vec<Job> =
+            [3.0, 5.0, 7.0, 11.0, 13.0, 17.0, 19.0, 23.0, 29.0].iter().enumerate().map(|(i, &p)| Job::new(i + 1, 0.0, 0.1).with_period(Some(p))).collect();
+        let stream = JobStream::new(jobs).unwrap();
+        let horizon = default_horizon(stream.jobs()).unwrap();
+        assert_eq!(horizon, 3_234_846_615.0);
+        assert!(matches!(release(&stream, horizon), Err(ScheduleError::TooManyInstances { .. })));
+        assert_eq!(release(&stream, 29.0).unwrap().jobs().len(), 10 + 6 + 5 + 3 + 3 + 2 + 2 + 2 + 1);
+    }
+}
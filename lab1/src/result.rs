@@ -0,0 +1,535 @@
+// 结构化的调度结果：逐作业记录、各道时间线与汇总指标，可导出为 JSON / CSV
+// 缺失的值（如未完成作业的结束时间）在 JSON 中为 null，在 CSV 中为空
+
+use crate::clock;
+use crate::gantt::{self, Slot};
+use crate::job::{Job, Segment};
+use crate::json::Value;
+
+#[derive(Clone, Debug)]
+pub struct JobRecord {
+    pub id: usize,
+    pub arrival: f64,
+    pub service: f64,
+    pub priority: i64,
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub turnaround: Option<f64>,
+    pub weighted_turnaround: Option<f64>,
+    pub waiting: Option<f64>,
+    pub response: Option<f64>,
+    pub segments: Vec<Segment>,
+    pub memory: u64,
+    pub admitted: Option<f64>, // 有主存限制时调入主存的时刻
+    pub address: Option<u64>,
+    pub deadline: Option<f64>,
+    pub lateness: Option<f64>,
+    pub task: Option<usize>, // 周期作业的实例所属的周期作业
+    pub tickets: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    pub jobs: usize,
+    pub completed: usize,
+    pub avg_turnaround: Option<f64>,
+    pub avg_weighted_turnaround: Option<f64>,
+    pub avg_waiting: Option<f64>,
+    pub avg_response: Option<f64>,
+    pub max_turnaround: Option<f64>,
+    pub p50_turnaround: Option<f64>,
+    pub p90_turnaround: Option<f64>,
+    pub p95_turnaround: Option<f64>,
+    pub makespan: f64,               // 从 0 时刻到最后一个作业完成
+    pub throughput: Option<f64>,     // 单位时间完成的作业数
+    pub utilization: Vec<f64>,       // 各道忙碌时间占 makespan 的比例
+    pub jain_index: Option<f64>,     // 带权周转时间的 Jain 公平性指数，1 为完全公平
+    pub max_waiting: Option<f64>,
+    pub p50_slowdown: Option<f64>,   // slowdown 即带权周转时间
+    pub p90_slowdown: Option<f64>,
+    pub p99_slowdown: Option<f64>,
+    pub max_slowdown: Option<f64>,
+    pub deadline_misses: Option<usize>, // 错过截止时间的作业数；没有作业带截止时间时为 None
+    pub max_lateness: Option<f64>,      // 延迟 = 完成时刻 - 截止时间
+    pub avg_tardiness: Option<f64>,     // 拖期 = max(延迟, 0)
+    pub max_tardiness: Option<f64>,
+}
+
+impl Metrics {
+    pub fn from_jobs(jobs: &[Job], m: usize) -> Self {
+        // 刻度模式下时长都是整刻度，按刻度求和后只除一次，平均值不带浮点尾差
+        let grid = jobs.iter().find_map(|j| j.grid);
+        let avg = |xs: &[f64]| match grid {
+            Some(ticks) if !xs.is_empty() => {
+                let ticks = ticks as f64;
+                Some(xs.iter().map(|x| (x * ticks).round()).sum::<f64>() / (xs.len() as f64 * ticks))
+            }
+            _ => mean(xs),
+        };
+        let mut turns: Vec<f64> = jobs.iter().filter_map(|j| j.turnaround()).collect();
+        let mut wturns: Vec<f64> = jobs.iter().filter_map(|j| j.weighted_turnaround()).collect();
+        let waits: Vec<f64> = jobs.iter().filter_map(|j| j.waiting()).collect();
+        let responses: Vec<f64> = jobs.iter().filter_map(|j| j.response()).collect();
+        let makespan = jobs.iter().filter_map(|j| j.end).fold(0.0, f64::max);
+        let mut busy = vec![0.0; m];
+        for s in jobs.iter().flat_map(|j| &j.segments) {
+            busy[s.channel] += s.end - s.start;
+        }
+        let utilization = busy
+            .iter()
+            .map(|b| match grid {
+                _ if makespan <= 0.0 => 0.0,
+                Some(ticks) => (b * ticks as f64).round() / (makespan * ticks as f64).round(),
+                None => b / makespan,
+            })
+            .collect();
+        let with_deadline = jobs.iter().any(|j| j.deadline.is_some());
+        let lateness: Vec<f64> = jobs.iter().filter_map(|j| j.lateness()).collect();
+        let tardiness: Vec<f64> = jobs.iter().filter_map(|j| j.tardiness()).collect();
+        turns.sort_by(f64::total_cmp);
+        wturns.sort_by(f64::total_cmp);
+        Metrics {
+            jobs: jobs.len(),
+            completed: turns.len(),
+            avg_turnaround: avg(&turns),
+            avg_weighted_turnaround: mean(&wturns),
+            avg_waiting: avg(&waits),
+            avg_response: avg(&responses),
+            max_turnaround: turns.last().copied(),
+            p50_turnaround: percentile(&turns, 50.0),
+            p90_turnaround: percentile(&turns, 90.0),
+            p95_turnaround: percentile(&turns, 95.0),
+            makespan,
+            throughput: (makespan > 0.0).then(|| turns.len() as f64 / makespan),
+            utilization,
+            jain_index: jain_index(&wturns),
+            max_waiting: waits.iter().copied().reduce(f64::max),
+            p50_slowdown: percentile(&wturns, 50.0),
+            p90_slowdown: percentile(&wturns, 90.0),
+            p99_slowdown: percentile(&wturns, 99.0),
+            max_slowdown: wturns.last().copied(),
+            deadline_misses: with_deadline.then(|| jobs.iter().filter(|j| j.missed_deadline()).count()),
+            max_lateness: lateness.iter().copied().reduce(f64::max),
+            avg_tardiness: avg(&tardiness),
+            max_tardiness: tardiness.iter().copied().reduce(f64::max),
+        }
+    }
+
+    pub fn avg_utilization(&self) -> Option<f64> {
+        mean(&self.utilization)
+    }
+}
+
+pub fn mean(xs: &[f64]) -> Option<f64> {
+    if xs.is_empty() { None } else { Some(xs.iter().sum::<f64>() / xs.len() as f64) }
+}
+
+// Jain 公平性指数 (Σx)² / (n·Σx²)，取值 [1/n, 1]
+fn jain_index(xs: &[f64]) -> Option<f64> {
+    let sum_sq: f64 = xs.iter().map(|x| x * x).sum();
+    (sum_sq > 0.0).then(|| xs.iter().sum::<f64>().powi(2) / (xs.len() as f64 * sum_sq))
+}
+
+// slowdown 分布的分组下界：[1, 2)、[2, 5)、[5, 10)、[10, ∞)
+pub const SLOWDOWN_BUCKETS: [f64; 4] = [1.0, 2.0, 5.0, 10.0];
+
+pub fn slowdown_histogram(jobs: &[Job]) -> [usize; 4] {
+    let mut counts = [0; 4];
+    for s in jobs.iter().filter_map(|j| j.weighted_turnaround()) {
+        let k = SLOWDOWN_BUCKETS.iter().rposition(|&lo| s >= lo).unwrap_or(0);
+        counts[k] += 1;
+    }
+    counts
+}
+
+// 等待时间超过阈值的作业（饥饿），按 id 升序
+pub fn starved(jobs: &[Job], threshold: f64) -> Vec<usize> {
+    let mut ids: Vec<usize> = jobs.iter().filter(|j| j.waiting().is_some_and(|w| w > threshold)).map(|j| j.id).collect();
+    ids.sort_unstable();
+    ids
+}
+
+// 最近秩百分位数，xs 须已升序排列
+pub fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[derive(Clone, Debug)]
+pub struct ScheduleResult {
+    pub algorithm: String,
+    pub channels: usize,
+    pub jobs: Vec<JobRecord>,
+    pub timelines: Vec<Vec<Slot>>,
+    pub metrics: Metrics,
+    pub clock: Option<f64>, // 0 时刻对应的时钟时刻；给出时导出中附带时刻列
+    pub degree: Option<usize>, // 两级调度时的多道程序度（此时 channels 为 CPU 数 1）
+}
+
+impl ScheduleResult {
+    pub fn new(algorithm: &str, channels: usize, jobs: &[Job]) -> Self {
+        let mut records: Vec<JobRecord> = jobs
+            .iter()
+            .map(|j| JobRecord {
+                id: j.id,
+                arrival: j.arrival,
+                service: j.service,
+                priority: j.priority,
+                start: j.start,
+                end: j.end,
+                turnaround: j.turnaround(),
+                weighted_turnaround: j.weighted_turnaround(),
+                waiting: j.waiting(),
+                response: j.response(),
+                segments: j.segments.clone(),
+                memory: j.memory,
+                admitted: j.admitted,
+                address: j.address,
+                deadline: j.deadline,
+                lateness: j.lateness(),
+                task: j.task,
+                tickets: j.tickets,
+            })
+            .collect();
+        records.sort_by_key(|r| r.id);
+        ScheduleResult {
+            algorithm: algorithm.to_string(),
+            channels,
+            jobs: records,
+            timelines: gantt::timelines(jobs, channels),
+            metrics: Metrics::from_jobs(jobs, channels),
+            clock: None,
+            degree: None,
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Option<f64>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_degree(mut self, degree: Option<usize>) -> Self {
+        self.degree = degree;
+        self
+    }
+
+    // CSV 中 m 列的值：两级调度时为多道程序度，否则为道数
+    pub fn m(&self) -> usize {
+        self.degree.unwrap_or(self.channels)
+    }
+
+    // 相对时刻 t 的时钟表示；没有时钟起点或 t 缺失时为 None
+    pub fn clock_time(&self, t: Option<f64>) -> Option<String> {
+        Some(clock::format(self.clock? + t?))
+    }
+
+    pub fn to_json_value(&self) -> Value {
+        let jobs = self
+            .jobs
+            .iter()
+            .map(|r| {
+                let segments = r
+                    .segments
+                    .iter()
+                    .map(|s| Value::Obj(vec![("channel".into(), s.channel.into()), ("start".into(), s.start.into()), ("end".into(), s.end.into())]))
+                    .collect();
+                let mut fields = vec![
+                    ("id".into(), r.id.into()),
+                    ("arrival".into(), r.arrival.into()),
+                    ("service".into(), r.service.into()),
+                    ("priority".into(), r.priority.into()),
+                    ("start".into(), r.start.into()),
+                    ("end".into(), r.end.into()),
+                    ("turnaround".into(), r.turnaround.into()),
+                    ("weighted_turnaround".into(), r.weighted_turnaround.into()),
+                    ("waiting".into(), r.waiting.into()),
+                    ("response".into(), r.response.into()),
+                    ("segments".into(), Value::Arr(segments)),
+                ];
+                if r.admitted.is_some() {
+                    fields.push(("memory".into(), Value::Num(r.memory as f64)));
+                    fields.push(("admitted".into(), r.admitted.into()));
+                    fields.push(("address".into(), r.address.map(|a| a as f64).into()));
+                }
+                if r.deadline.is_some() {
+                    fields.push(("deadline".into(), r.deadline.into()));
+                    fields.push(("lateness".into(), r.lateness.into()));
+                }
+                if let Some(task) = r.task {
+                    fields.push(("task".into(), task.into()));
+                }
+                if r.tickets != 1 {
+                    fields.push(("tickets".into(), Value::Num(r.tickets as f64)));
+                }
+                if self.clock.is_some() {
+                    fields.push(("arrival_clock".into(), self.clock_time(Some(r.arrival)).as_deref().into()));
+                    fields.push(("start_clock".into(), self.clock_time(r.start).as_deref().into()));
+                    fields.push(("end_clock".into(), self.clock_time(r.end).as_deref().into()));
+                }
+                Value::Obj(fields)
+            })
+            .collect();
+        let timelines = self
+            .timelines
+            .iter()
+            .map(|line| {
+                Value::Arr(
+                    line.iter()
+                        .map(|s| Value::Obj(vec![("job".into(), s.job.into()), ("start".into(), s.start.into()), ("end".into(), s.end.into())]))
+                        .collect(),
+                )
+            })
+            .collect();
+        let mut fields = vec![("algorithm".into(), self.algorithm.as_str().into()), ("channels".into(), self.channels.into())];
+        if let Some(degree) = self.degree {
+            fields.push(("degree".into(), degree.into()));
+        }
+        if let Some(origin) = self.clock {
+            fields.push(("clock_origin".into(), clock::format(origin).as_str().into()));
+        }
+        fields.push(("jobs".into(), Value::Arr(jobs)));
+        fields.push(("timelines".into(), Value::Arr(timelines)));
+        fields.push(("metrics".into(), self.metrics_json()));
+        Value::Obj(fields)
+    }
+
+    fn metrics_json(&self) -> Value {
+        let m = &self.metrics;
+        let mut fields = vec![
+            ("jobs".into(), m.jobs.into()),
+            ("completed".into(), m.completed.into()),
+            ("avg_turnaround".into(), m.avg_turnaround.into()),
+            ("avg_weighted_turnaround".into(), m.avg_weighted_turnaround.into()),
+            ("avg_waiting".into(), m.avg_waiting.into()),
+            ("avg_response".into(), m.avg_response.into()),
+            ("max_turnaround".into(), m.max_turnaround.into()),
+            ("p50_turnaround".into(), m.p50_turnaround.into()),
+            ("p90_turnaround".into(), m.p90_turnaround.into()),
+            ("p95_turnaround".into(), m.p95_turnaround.into()),
+            ("makespan".into(), m.makespan.into()),
+            ("throughput".into(), m.throughput.into()),
+            ("utilization".into(), Value::Arr(m.utilization.iter().map(|&u| u.into()).collect())),
+            ("jain_index".into(), m.jain_index.into()),
+            ("max_waiting".into(), m.max_waiting.into()),
+            ("p50_slowdown".into(), m.p50_slowdown.into()),
+            ("p90_slowdown".into(), m.p90_slowdown.into()),
+            ("p99_slowdown".into(), m.p99_slowdown.into()),
+            ("max_slowdown".into(), m.max_slowdown.into()),
+        ];
+        if let Some(misses) = m.deadline_misses {
+            fields.push(("deadline_misses".into(), misses.into()));
+            fields.push(("max_lateness".into(), m.max_lateness.into()));
+            fields.push(("avg_tardiness".into(), m.avg_tardiness.into()));
+            fields.push(("max_tardiness".into(), m.max_tardiness.into()));
+        }
+        Value::Obj(fields)
+    }
+
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_pretty()
+    }
+
+    // 逐作业 CSV；有作业带截止时间时附加截止时间与延迟列，有时钟起点时在末尾附加到达/开始/结束的时刻列
+    pub fn jobs_csv(&self, header: bool) -> String {
+        let with_deadline = self.metrics.deadline_misses.is_some();
+        let mut out = String::new();
+        if header {
+            out.push_str("algorithm,m,id,arrival,service,priority,start,end,turnaround,weighted_turnaround,waiting,response");
+            out.push_str(if with_deadline { ",deadline,lateness" } else { "" });
+            out.push_str(if self.clock.is_some() { ",arrival_clock,start_clock,end_clock\n" } else { "\n" });
+        }
+        for r in &self.jobs {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{}",
+                csv_field(&self.algorithm),
+                self.m(),
+                r.id,
+                r.arrival,
+                r.service,
+                r.priority,
+                opt(r.start),
+                opt(r.end),
+                opt(r.turnaround),
+                opt(r.weighted_turnaround),
+                opt(r.waiting),
+                opt(r.response)
+            ));
+            if with_deadline {
+                out.push_str(&format!(",{},{}", opt(r.deadline), opt(r.lateness)));
+            }
+            if self.clock.is_some() {
+                let t = |v: Option<f64>| self.clock_time(v).unwrap_or_default();
+                out.push_str(&format!(",{},{},{}", t(Some(r.arrival)), t(r.start), t(r.end)));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // 各道时间线 CSV，空闲区间的 job 为空
+    pub fn timeline_csv(&self, header: bool) -> String {
+        let mut out = String::new();
+        if header {
+            out.push_str("algorithm,m,channel,job,start,end");
+            out.push_str(if self.clock.is_some() { ",start_clock,end_clock\n" } else { "\n" });
+        }
+        for (k, line) in self.timelines.iter().enumerate() {
+            for s in line {
+                let job = s.job.map_or(String::new(), |id| id.to_string());
+                out.push_str(&format!("{},{},{},{},{},{}", csv_field(&self.algorithm), self.m(), k, job, s.start, s.end));
+                if self.clock.is_some() {
+                    let t = |v: f64| self.clock_time(Some(v)).unwrap_or_default();
+                    out.push_str(&format!(",{},{}", t(s.start), t(s.end)));
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    // 汇总指标 CSV，一次运行一行；各道利用率只给出平均值，逐道数值见 JSON。有作业带截止时间时附加截止时间指标列
+    pub fn metrics_csv(&self, header: bool) -> String {
+        let m = &self.metrics;
+        let mut out = String::new();
+        if header {
+            out.push_str("algorithm,m,jobs,completed,avg_turnaround,avg_weighted_turnaround,avg_waiting,avg_response,");
+            out.push_str("max_turnaround,p50_turnaround,p90_turnaround,p95_turnaround,makespan,throughput,avg_utilization,");
+            out.push_str("jain_index,max_waiting,p50_slowdown,p90_slowdown,p99_slowdown,max_slowdown");
+            out.push_str(if m.deadline_misses.is_some() { ",deadline_misses,max_lateness,avg_tardiness,max_tardiness\n" } else { "\n" });
+        }
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            csv_field(&self.algorithm),
+            self.m(),
+            m.jobs,
+            m.completed,
+            opt(m.avg_turnaround),
+            opt(m.avg_weighted_turnaround),
+            opt(m.avg_waiting),
+            opt(m.avg_response),
+            opt(m.max_turnaround),
+            opt(m.p50_turnaround),
+            opt(m.p90_turnaround),
+            opt(m.p95_turnaround),
+            m.makespan,
+            opt(m.throughput),
+            opt(m.avg_utilization()),
+            opt(m.jain_index),
+            opt(m.max_waiting),
+            opt(m.p50_slowdown),
+            opt(m.p90_slowdown),
+            opt(m.p99_slowdown),
+            opt(m.max_slowdown)
+        ));
+        if let Some(misses) = m.deadline_misses {
+            out.push_str(&format!(",{},{},{},{}", misses, opt(m.max_lateness), opt(m.avg_tardiness), opt(m.max_tardiness)));
+        }
+        out.push('\n');
+        out
+    }
+}
+
+fn opt(v: Option<f64>) -> String {
+    v.map_or(String::new(), |v| v.to_string())
+}
+
+// 含逗号或引号的字段加引号（算法名如 "RR(q=1, cs=0.5)"）
+pub fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::JobStream;
+    use crate::scheduler::{simulate, Fcfs, Scheduler, SimOptions, Sjf};
+
+    // 教材样例 A 与 B（lab1 demo 中的两组作业流）
+    const SAMPLE_A: [(usize, f64, f64); 5] = [(1, 0.0, 3.0), (2, 2.0, 6.0), (3, 4.0, 4.0), (4, 6.0, 5.0), (5, 8.0, 2.0)];
+    const SAMPLE_B: [(usize, f64, f64); 6] = [(1, 0.0, 8.0), (2, 1.0, 4.0), (3, 2.0, 9.0), (4, 3.0, 5.0), (5, 10.0, 2.0), (6, 10.0, 1.0)];
+
+    fn run(sample: &[(usize, f64, f64)], m: usize, policy: &dyn Scheduler) -> Vec<Job> {
+        let stream = JobStream::new(sample.iter().map(|&(id, a, s)| Job::new(id, a, s)).collect()).unwrap();
+        simulate(&stream, m, policy, SimOptions::default()).unwrap()
+    }
+
+    fn close(a: Option<f64>, b: f64) -> bool {
+        a.is_some_and(|a| (a - b).abs() < 1e-9)
+    }
+
+    #[test]
+    fn metrics_on_sample_a() {
+        // FCFS：周转 3, 7, 9, 12, 12，等待 0, 1, 5, 7, 10
+        let m = Metrics::from_jobs(&run(&SAMPLE_A, 1, &Fcfs), 1);
+        assert_eq!((m.jobs, m.completed), (5, 5));
+        assert_eq!((m.avg_turnaround, m.avg_waiting, m.avg_response), (Some(8.6), Some(4.6), Some(4.6)));
+        assert!(close(m.avg_weighted_turnaround, (1.0 + 7.0 / 6.0 + 9.0 / 4.0 + 12.0 / 5.0 + 6.0) / 5.0));
+        assert_eq!((m.max_turnaround, m.p50_turnaround, m.p90_turnaround, m.p95_turnaround), (Some(12.0), Some(9.0), Some(12.0), Some(12.0)));
+        assert_eq!((m.makespan, m.throughput, m.utilization.clone()), (20.0, Some(0.25), vec![1.0]));
+        assert_eq!((m.deadline_misses, m.max_lateness, m.avg_tardiness), (None, None, None));
+    }
+
+    #[test]
+    fn metrics_on_sample_b() {
+        // SJF 单道：周转 8, 11, 27, 17, 5, 3，等待 0, 7, 18, 12, 3, 2
+        let m = Metrics::from_jobs(&run(&SAMPLE_B, 1, &Sjf), 1);
+        assert!(close(m.avg_turnaround, 71.0 / 6.0));
+        assert_eq!((m.avg_waiting, m.avg_response), (Some(7.0), Some(7.0)));
+        assert_eq!((m.p50_turnaround, m.p90_turnaround, m.max_turnaround), (Some(8.0), Some(27.0), Some(27.0)));
+        assert_eq!((m.makespan, m.throughput), (29.0, Some(6.0 / 29.0)));
+        // FCFS 两道：0 道忙 0-8、8-13、13-15，1 道忙 1-5、5-14、14-15
+        let m = Metrics::from_jobs(&run(&SAMPLE_B, 2, &Fcfs), 2);
+        assert_eq!((m.makespan, m.utilization.clone()), (15.0, vec![1.0, 14.0 / 15.0]));
+        assert!(close(m.avg_utilization(), (1.0 + 14.0 / 15.0) / 2.0));
+        // 没有作业完成时各项平均值缺失
+        let m = Metrics::from_jobs(&[Job::new(1, 0.0, 1.0)], 1);
+        assert_eq!((m.completed, m.avg_turnaround, m.throughput, m.p50_turnaround), (0, None, None, None));
+    }
+
+    #[test]
+    fn fairness_on_samples() {
+        // 样例 A、FCFS：slowdown 1, 7/6, 9/4, 12/5, 6
+        let a = run(&SAMPLE_A, 1, &Fcfs);
+        let m = Metrics::from_jobs(&a, 1);
+        assert!(close(m.jain_index, 0.6679743139370048));
+        assert_eq!((m.max_waiting, m.p50_slowdown, m.p90_slowdown, m.p99_slowdown, m.max_slowdown), (Some(10.0), Some(2.25), Some(6.0), Some(6.0), Some(6.0)));
+        assert_eq!(slowdown_histogram(&a), [2, 2, 1, 0]);
+        assert_eq!(starved(&a, 5.0), [4, 5]);
+        assert_eq!(starved(&a, 10.0), [] as [usize; 0]);
+        // 样例 B、SJF：slowdown 1, 11/4, 3, 17/5, 5/2, 3，长作业 3、4 等待最久
+        let b = run(&SAMPLE_B, 1, &Sjf);
+        let m = Metrics::from_jobs(&b, 1);
+        assert!(close(m.jain_index, 0.9199485417018799));
+        assert_eq!((m.max_waiting, m.p50_slowdown, m.max_slowdown), (Some(18.0), Some(2.75), Some(3.4)));
+        assert_eq!(slowdown_histogram(&b), [1, 5, 0, 0]);
+        assert_eq!(starved(&b, 10.0), [3, 4]);
+    }
+
+    #[test]
+    fn jain_index_bounds() {
+        // 全部相等时为 1，只有一个非零值时为下界 1/n
+        assert!(close(jain_index(&[3.0; 4]), 1.0));
+        assert!(close(jain_index(&[0.0, 0.0, 0.0, 8.0]), 0.25));
+        assert_eq!(jain_index(&[]), None);
+        assert_eq!(jain_index(&[0.0, 0.0]), None);
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let xs: Vec<f64> = (1..=10).map(f64::from).collect();
+        let at = |p| percentile(&xs, p);
+        // 秩 = ⌈p/100 · n⌉，不在相邻两值之间插值
+        assert_eq!((at(50.0), at(55.0), at(90.0), at(91.0), at(99.0)), (Some(5.0), Some(6.0), Some(9.0), Some(10.0), Some(10.0)));
+        assert_eq!((at(0.0), at(100.0)), (Some(1.0), Some(10.0)));
+        assert_eq!(percentile(&[2.5, 7.5], 50.0), Some(2.5));
+        assert_eq!(percentile(&[4.0; 7], 95.0), Some(4.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+}
@@ -0,0 +1,1650 @@
+// 调度策略与共享的离散事件引擎
+// 各算法只需实现 Scheduler::select（"就绪队列中下一个运行哪个作业"），
+// 时间推进、道分配、结果记录都由 simulate 统一完成
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeSet, BinaryHeap};
+use std::fmt;
+
+use crate::job::{Job, JobStream, Segment};
+use crate::memory::{Memory, MemoryModel};
+use crate::queue::ReadyQueue;
+use crate::rng::Rng;
+use crate::trace::{Candidate, EventKind, Trace};
+
+// 调度输入不合法：作业流由 JobStream::new 校验，道数由 simulate 校验
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScheduleError {
+    NoChannels,
+    DuplicateId(usize),
+    NonFinite { id: usize, field: &'static str },
+    NegativeArrival { id: usize, arrival: f64 },
+    NonPositiveService { id: usize, service: f64 },
+    OffTick { id: usize, field: &'static str, value: f64, ticks: u32 },
+    ParamOffTick { param: &'static str, value: f64, ticks: u32 },
+    MemoryExceeded { id: usize, memory: u64, capacity: u64 },
+    DeadlineBeforeArrival { id: usize, deadline: f64, arrival: f64 },
+    NonPositivePeriod { id: usize, period: f64 },
+    NoTickets(usize),
+    TooManyInstances { count: f64, horizon: f64 },
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::NoChannels => write!(f, "道数必须至少为 1"),
+            ScheduleError::DuplicateId(id) => write!(f, "作业 id {} 重复", id),
+            ScheduleError::NonFinite { id, field } => write!(f, "作业 {} 的 {} 不是有限数", id, field),
+            ScheduleError::NegativeArrival { id, arrival } => write!(f, "作业 {} 的到达时间 {} 为负", id, arrival),
+            ScheduleError::NonPositiveService { id, service } => write!(f, "作业 {} 的运行时间 {} 必须为正", id, service),
+            ScheduleError::OffTick { id, field, value, ticks } => {
+                write!(f, "作业 {} 的 {} = {} 不是 1/{} 分钟的整数倍，请增大 --ticks", id, field, value, ticks)
+            }
+            ScheduleError::ParamOffTick { param, value, ticks } => {
+                write!(f, "{} {} 不是 1/{} 分钟的整数倍，请增大 --ticks 或改为刻度的整数倍", param, value, ticks)
+            }
+            ScheduleError::MemoryExceeded { id, memory, capacity } => {
+                write!(f, "作业 {} 需要主存 {}，超过总容量 {}，永远无法调入", id, memory, capacity)
+            }
+            ScheduleError::DeadlineBeforeArrival { id, deadline, arrival } => {
+                write!(f, "作业 {} 的截止时间 {} 早于到达时间 {}", id, deadline, arrival)
+            }
+            ScheduleError::NonPositivePeriod { id, period } => write!(f, "作业 {} 的周期 {} 必须为正", id, period),
+            ScheduleError::NoTickets(id) => write!(f, "作业 {} 的彩票数必须为正", id),
+            ScheduleError::TooManyInstances { count, horizon } => write!(
+                f,
+                "展开到 {} 时共有 {} 个周期作业实例，超过上限 {}，请用 --horizon 给出更短的展开区间",
+                horizon,
+                count,
+                crate::realtime::MAX_INSTANCES
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+pub trait Scheduler {
+    fn name(&self) -> &'static str;
+
+    // 从就绪队列中选出下一个运行的作业，返回其在 ready 中的下标（调用时 ready 非空）
+    // ready 按进入就绪队列的先后排列；按关键字挑选的策略通过 tie 处理关键字相等的情况
+    fn select(&self, ready: &[Job], now: f64, tie: &mut TieBreaker) -> usize;
+
+    // 与时间无关的排序关键字：Some 表示 select 总是挑 key 最小者（相等时按取舍规则，fifo_ties 时按进入就绪队列的先后），
+    // 引擎据此用堆维护就绪队列。关键字随时间变化的策略（如 HRRN）返回 None，由 select 在整个队列上挑选
+    fn key(&self, _job: &Job) -> Option<f64> {
+        None
+    }
+
+    // key 是否总为 Some：由策略直接声明，引擎据此决定就绪队列用堆还是数组
+    fn keyed(&self) -> bool {
+        false
+    }
+
+    // 关键字相等时总按进入就绪队列的先后，不受取舍规则影响
+    fn fifo_ties(&self) -> bool {
+        false
+    }
+
+    // 讲解模式（--explain）中的挑选依据：名称与是否取大者；None 表示只看队列顺序（如 RR）
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        None
+    }
+
+    // 作业在 now 时刻的挑选依据取值，缺省为 key
+    fn score(&self, job: &Job, _now: f64) -> f64 {
+        self.key(job).unwrap_or(0.0)
+    }
+
+    // 抢占式策略：每个事件时刻都把正在运行的作业与就绪作业放在一起重新挑选，
+    // 落选的运行作业被抢占并回到就绪队列末尾
+    fn preemptive(&self) -> bool {
+        false
+    }
+
+    // 时间片：Some(q) 表示作业每次至多连续运行 q，用完后回到就绪队列末尾
+    fn quantum(&self, _job: &Job) -> Option<f64> {
+        None
+    }
+
+    // 上下文切换开销：某道从一个作业换到另一个作业时，需先空转这么长时间
+    fn switch_cost(&self) -> f64 {
+        0.0
+    }
+
+    // 周期性定时事件的周期（如 MLFQ 的优先级提升）
+    fn period(&self) -> Option<f64> {
+        None
+    }
+
+    // 以时间计的策略参数（名称, 取值），刻度模式下须落在刻度上；带时间片、切换开销或定时周期的策略须列出
+    fn timings(&self) -> Vec<(&'static str, f64)> {
+        Vec::new()
+    }
+
+    // 作业到达、时间片用完、定时事件时的回调，可修改作业的调度状态（如所在队列）
+    // on_period 对就绪与运行中的作业都会调用，waited 表示作业自最近一次进入就绪队列起已等待满一个周期
+    // （运行中的作业与刚进入就绪队列的作业为 false）
+    fn on_arrive(&self, _job: &mut Job, _now: f64) {}
+
+    fn on_expire(&self, _job: &mut Job, _now: f64) {}
+
+    fn on_period(&self, _job: &mut Job, _now: f64, _waited: bool) {}
+
+    // 新作业进入就绪队列时，global 为最近一次从队列中取出的作业的 key（单道时即就绪与运行中作业 key 的最小值）
+    fn on_join(&self, _job: &mut Job, _global: f64) {}
+
+    // 比例份额调度：按作业的彩票数分配 CPU，输出中对比实际所得份额与彩票份额
+    fn proportional(&self) -> bool {
+        false
+    }
+
+    // 输出中显示的名称（可带参数）
+    fn label(&self) -> String {
+        self.name().to_string()
+    }
+}
+
+// 取 key 最小者的下标；相等时取靠前的（即先进入就绪队列的）
+fn argmin_by_key(ready: &[Job], key: impl Fn(&Job) -> f64) -> usize {
+    let mut best = 0;
+    for i in 1..ready.len() {
+        if key(&ready[i]).total_cmp(&key(&ready[best])).is_lt() {
+            best = i;
+        }
+    }
+    best
+}
+
+// 关键字相等时选哪个作业
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TieBreak {
+    Fifo,           // 先进入就绪队列者（缺省）
+    LowerId,        // id 小者
+    EarlierArrival, // 到达早者，仍相同时按就绪队列顺序
+    Random(u64),    // 以给定种子随机选取
+}
+
+// 同一时刻有多条空闲道时先分派哪条
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelTie {
+    Lowest,      // 编号小的道先分派（缺省）
+    Random(u64), // 以给定种子随机排列空闲道
+}
+
+// 一次调度过程中的取舍状态：随机规则的发生器只在真的出现相等时才前进（道的随机分派只在有就绪作业、
+// 且空闲道多于一条时抽取），结果由种子唯一确定
+pub struct TieBreaker {
+    rule: TieBreak,
+    channel_rule: ChannelTie,
+    rng: Rng,
+    channel_rng: Rng,
+    lottery: Option<Rng>, // 彩票调度抽签用，第一次抽签时以策略给出的种子创建
+    keep: usize, // 抢占时候选最前面的 keep 个是运行中的作业，相等时它们保留道
+}
+
+impl TieBreaker {
+    pub fn new(rule: TieBreak, channel_rule: ChannelTie) -> Self {
+        let seed = |r: Option<u64>| Rng::new(r.unwrap_or(0));
+        TieBreaker {
+            rule,
+            channel_rule,
+            rng: seed(match rule { TieBreak::Random(s) => Some(s), _ => None }),
+            channel_rng: seed(match channel_rule { ChannelTie::Random(s) => Some(s), _ => None }),
+            lottery: None,
+            keep: 0,
+        }
+    }
+
+    // 取 key 最小者的下标，多个相等时按规则取舍
+    pub fn argmin_by_key(&mut self, ready: &[Job], key: impl Fn(&Job) -> f64) -> usize {
+        let best = key(&ready[argmin_by_key(ready, &key)]);
+        let tied: Vec<usize> = (0..ready.len()).filter(|&i| key(&ready[i]) == best).collect();
+        if tied.len() == 1 || tied[0] < self.keep {
+            return tied[0];
+        }
+        match self.rule {
+            TieBreak::Fifo => tied[0],
+            TieBreak::LowerId => tied.into_iter().min_by_key(|&i| ready[i].id).unwrap(),
+            TieBreak::EarlierArrival => tied.into_iter().min_by(|&a, &b| ready[a].arrival.total_cmp(&ready[b].arrival)).unwrap(),
+            TieBreak::Random(_) => tied[self.rng.below(tied.len())],
+        }
+    }
+
+    pub fn rule(&self) -> TieBreak {
+        self.rule
+    }
+
+    // 按彩票数加权抽签，返回中签作业的下标；同一次调度中的抽签序列由 seed 唯一确定
+    pub fn lottery(&mut self, ready: &[Job], seed: u64) -> usize {
+        let total: u64 = ready.iter().map(|j| j.tickets).sum();
+        let rng = self.lottery.get_or_insert_with(|| Rng::new(seed));
+        let mut ticket = rng.next_u64() % total;
+        for (i, j) in ready.iter().enumerate() {
+            if ticket < j.tickets {
+                return i;
+            }
+            ticket -= j.tickets;
+        }
+        ready.len() - 1
+    }
+
+    // 空闲道的分派顺序：free 按编号排列，随机规则下就地打乱；只有一条空闲道时不抽取
+    fn channel_order(&mut self, free: &mut [usize]) {
+        let ChannelTie::Random(_) = self.channel_rule else { return };
+        for i in (1..free.len()).rev() {
+            free.swap(i, self.channel_rng.below(i + 1));
+        }
+    }
+}
+
+// 1) FCFS：到达最早者优先
+pub struct Fcfs;
+
+impl Scheduler for Fcfs {
+    fn name(&self) -> &'static str {
+        "FCFS"
+    }
+
+    fn select(&self, ready: &[Job], _now: f64, tie: &mut TieBreaker) -> usize {
+        tie.argmin_by_key(ready, |j| j.arrival)
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        Some(job.arrival)
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("到达时间", false))
+    }
+}
+
+// 2) SJF（非抢占）：估计运行时间最短者优先
+pub struct Sjf;
+
+impl Scheduler for Sjf {
+    fn name(&self) -> &'static str {
+        "SJF"
+    }
+
+    fn select(&self, ready: &[Job], _now: f64, tie: &mut TieBreaker) -> usize {
+        tie.argmin_by_key(ready, |j| j.service)
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        Some(job.service)
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("运行时间", false))
+    }
+}
+
+// 3) HRRN：响应比 (等待时间 + 运行时间) / 运行时间 最高者优先
+pub struct Hrrn;
+
+pub fn response_ratio(job: &Job, now: f64) -> f64 {
+    (now - job.arrival + job.service) / job.service
+}
+
+impl Scheduler for Hrrn {
+    fn name(&self) -> &'static str {
+        "HRRN"
+    }
+
+    fn select(&self, ready: &[Job], now: f64, tie: &mut TieBreaker) -> usize {
+        tie.argmin_by_key(ready, |j| -response_ratio(j, now))
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("响应比", true))
+    }
+
+    fn score(&self, job: &Job, now: f64) -> f64 {
+        response_ratio(job, now)
+    }
+}
+
+// 4) SRTF（抢占式 SJF）：剩余运行时间最短者优先，新到达的更短作业可抢占正在运行的作业
+pub struct Srtf;
+
+impl Scheduler for Srtf {
+    fn name(&self) -> &'static str {
+        "SRTF"
+    }
+
+    fn select(&self, ready: &[Job], _now: f64, tie: &mut TieBreaker) -> usize {
+        tie.argmin_by_key(ready, |j| j.remaining)
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        Some(job.remaining)
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("剩余时间", false))
+    }
+
+    fn preemptive(&self) -> bool {
+        true
+    }
+}
+
+// 5) RR（时间片轮转）：就绪队列先进先出，每次至多运行一个时间片
+pub struct RoundRobin {
+    pub quantum: f64,
+    pub switch_cost: f64,
+}
+
+impl Scheduler for RoundRobin {
+    fn name(&self) -> &'static str {
+        "RR"
+    }
+
+    // 队列顺序本身就是关键字，不存在相等
+    fn select(&self, _ready: &[Job], _now: f64, _tie: &mut TieBreaker) -> usize {
+        0
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, _job: &Job) -> Option<f64> {
+        Some(0.0)
+    }
+
+    fn fifo_ties(&self) -> bool {
+        true
+    }
+
+    fn quantum(&self, _job: &Job) -> Option<f64> {
+        Some(self.quantum)
+    }
+
+    fn switch_cost(&self) -> f64 {
+        self.switch_cost
+    }
+
+    fn timings(&self) -> Vec<(&'static str, f64)> {
+        vec![("时间片", self.quantum), ("切换开销", self.switch_cost)]
+    }
+
+    fn label(&self) -> String {
+        if self.switch_cost > 0.0 {
+            format!("RR(q={}, cs={})", self.quantum, self.switch_cost)
+        } else {
+            format!("RR(q={})", self.quantum)
+        }
+    }
+}
+
+// 6) MLFQ（多级反馈队列）：新作业进入 0 级队列；用完本级时间片降一级（最低级保持不变）；
+// 高级队列非空时低级作业不运行，且高级作业到达时抢占低级作业；每隔 boost 把所有作业提升回 0 级
+pub struct Mlfq {
+    pub quanta: Vec<f64>, // 各级时间片，长度即级数
+    pub boost: Option<f64>,
+}
+
+impl Mlfq {
+    fn set_level(job: &mut Job, level: usize, now: f64) {
+        if job.level_log.last().map(|&(_, l)| l) != Some(level) {
+            job.level_log.push((now, level));
+        }
+        job.level = level;
+    }
+}
+
+impl Scheduler for Mlfq {
+    fn name(&self) -> &'static str {
+        "MLFQ"
+    }
+
+    // 同级队列内先进先出，不受取舍规则影响
+    fn select(&self, ready: &[Job], _now: f64, _tie: &mut TieBreaker) -> usize {
+        argmin_by_key(ready, |j| j.level as f64)
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        Some(job.level as f64)
+    }
+
+    fn fifo_ties(&self) -> bool {
+        true
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("队列级别", false))
+    }
+
+    fn preemptive(&self) -> bool {
+        true
+    }
+
+    fn quantum(&self, job: &Job) -> Option<f64> {
+        Some(self.quanta[job.level])
+    }
+
+    fn period(&self) -> Option<f64> {
+        self.boost
+    }
+
+    fn timings(&self) -> Vec<(&'static str, f64)> {
+        let mut out: Vec<(&'static str, f64)> = self.quanta.iter().map(|&q| ("各级时间片", q)).collect();
+        out.extend(self.boost.map(|b| ("提升周期", b)));
+        out
+    }
+
+    fn on_arrive(&self, job: &mut Job, now: f64) {
+        Mlfq::set_level(job, 0, now);
+    }
+
+    fn on_expire(&self, job: &mut Job, now: f64) {
+        let level = (job.level + 1).min(self.quanta.len() - 1);
+        Mlfq::set_level(job, level, now);
+    }
+
+    fn on_period(&self, job: &mut Job, now: f64, _waited: bool) {
+        Mlfq::set_level(job, 0, now);
+    }
+
+    fn label(&self) -> String {
+        let quanta: Vec<String> = self.quanta.iter().map(|q| q.to_string()).collect();
+        match self.boost {
+            Some(b) => format!("MLFQ(q={}, boost={})", quanta.join("/"), b),
+            None => format!("MLFQ(q={})", quanta.join("/")),
+        }
+    }
+}
+
+// 7) 静态优先级：优先数最小者优先，分非抢占与抢占两种；
+// 可选老化：每隔 aging，就绪队列中已等待满 aging 的作业优先数减 1，避免低优先级作业饿死；
+// 老化只计本次在就绪队列中的等待，作业被抢占回到就绪队列时提升清零、重新计时
+pub struct Priority {
+    pub preemptive: bool,
+    pub aging: Option<f64>,
+}
+
+impl Scheduler for Priority {
+    fn name(&self) -> &'static str {
+        if self.preemptive { "PPRIO" } else { "PRIO" }
+    }
+
+    fn select(&self, ready: &[Job], _now: f64, tie: &mut TieBreaker) -> usize {
+        tie.argmin_by_key(ready, |j| j.effective_priority() as f64)
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        Some(job.effective_priority() as f64)
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("优先数", false))
+    }
+
+    fn preemptive(&self) -> bool {
+        self.preemptive
+    }
+
+    fn period(&self) -> Option<f64> {
+        self.aging
+    }
+
+    fn timings(&self) -> Vec<(&'static str, f64)> {
+        self.aging.map(|a| ("老化周期", a)).into_iter().collect()
+    }
+
+    fn on_period(&self, job: &mut Job, _now: f64, waited: bool) {
+        if waited {
+            job.aged += 1;
+        }
+    }
+
+    fn label(&self) -> String {
+        match self.aging {
+            Some(a) => format!("{}(aging={})", self.name(), a),
+            None => self.name().to_string(),
+        }
+    }
+}
+
+// 8) EDF（最早截止时间优先）：绝对截止时间最早者优先，分抢占与非抢占两种；没有截止时间的作业排在最后
+pub struct Edf {
+    pub preemptive: bool,
+}
+
+fn deadline_key(job: &Job) -> f64 {
+    job.deadline.unwrap_or(f64::INFINITY)
+}
+
+impl Scheduler for Edf {
+    fn name(&self) -> &'static str {
+        if self.preemptive { "EDF" } else { "NPEDF" }
+    }
+
+    fn select(&self, ready: &[Job], _now: f64, tie: &mut TieBreaker) -> usize {
+        tie.argmin_by_key(ready, deadline_key)
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        Some(deadline_key(job))
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("截止时间", false))
+    }
+
+    fn preemptive(&self) -> bool {
+        self.preemptive
+    }
+}
+
+// 9) RM（单调速率）：周期最短者优先的静态优先级，抢占式；非周期作业排在最后
+pub struct RateMonotonic;
+
+fn period_key(job: &Job) -> f64 {
+    job.period.unwrap_or(f64::INFINITY)
+}
+
+impl Scheduler for RateMonotonic {
+    fn name(&self) -> &'static str {
+        "RM"
+    }
+
+    fn select(&self, ready: &[Job], _now: f64, tie: &mut TieBreaker) -> usize {
+        tie.argmin_by_key(ready, period_key)
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        Some(period_key(job))
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("周期", false))
+    }
+
+    fn preemptive(&self) -> bool {
+        true
+    }
+}
+
+// 10) 彩票调度：每次分派时按就绪作业持有的彩票数加权抽签，中签者运行一个时间片后回到就绪队列；
+// 长期来看各作业所得 CPU 份额趋于其彩票份额。抽签序列由种子决定，结果可复现
+pub struct Lottery {
+    pub quantum: f64,
+    pub switch_cost: f64,
+    pub seed: u64,
+}
+
+impl Scheduler for Lottery {
+    fn name(&self) -> &'static str {
+        "LOTTERY"
+    }
+
+    fn select(&self, ready: &[Job], _now: f64, tie: &mut TieBreaker) -> usize {
+        tie.lottery(ready, self.seed)
+    }
+
+    // 讲解中候选表列出各作业的彩票数
+    fn score(&self, job: &Job, _now: f64) -> f64 {
+        job.tickets as f64
+    }
+
+    fn quantum(&self, _job: &Job) -> Option<f64> {
+        Some(self.quantum)
+    }
+
+    fn switch_cost(&self) -> f64 {
+        self.switch_cost
+    }
+
+    fn timings(&self) -> Vec<(&'static str, f64)> {
+        vec![("时间片", self.quantum), ("切换开销", self.switch_cost)]
+    }
+
+    fn proportional(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        format!("LOTTERY(q={}, seed={})", self.quantum, self.seed)
+    }
+}
+
+// 11) 步幅调度：确定性的比例份额调度。作业的步幅为 STRIDE_UNIT / 彩票数，每次分派行程值最小者，
+// 它每用完一个时间片行程值增加一个步幅。新作业的行程值为全局行程值加一个步幅（Waldspurger），
+// 晚到的作业不会因行程值从 0 起算而独占 CPU
+pub struct Stride {
+    pub quantum: f64,
+    pub switch_cost: f64,
+}
+
+const STRIDE_UNIT: f64 = 10000.0;
+
+impl Stride {
+    fn stride(job: &Job) -> f64 {
+        STRIDE_UNIT / job.tickets as f64
+    }
+}
+
+impl Scheduler for Stride {
+    fn name(&self) -> &'static str {
+        "STRIDE"
+    }
+
+    fn select(&self, ready: &[Job], _now: f64, tie: &mut TieBreaker) -> usize {
+        tie.argmin_by_key(ready, |j| j.pass)
+    }
+
+    fn keyed(&self) -> bool {
+        true
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        Some(job.pass)
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        Some(("行程值", false))
+    }
+
+    fn quantum(&self, _job: &Job) -> Option<f64> {
+        Some(self.quantum)
+    }
+
+    fn switch_cost(&self) -> f64 {
+        self.switch_cost
+    }
+
+    fn timings(&self) -> Vec<(&'static str, f64)> {
+        vec![("时间片", self.quantum), ("切换开销", self.switch_cost)]
+    }
+
+    fn on_join(&self, job: &mut Job, global: f64) {
+        job.pass = global + Stride::stride(job);
+    }
+
+    fn on_expire(&self, job: &mut Job, _now: f64) {
+        job.pass += Stride::stride(job);
+    }
+
+    fn proportional(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        format!("STRIDE(q={})", self.quantum)
+    }
+}
+
+// 带参数策略的配置
+#[derive(Clone, Debug)]
+pub struct SchedulerConfig {
+    pub quantum: f64,
+    pub switch_cost: f64,
+    pub levels: usize,              // MLFQ 级数
+    pub level_quanta: Vec<f64>,     // MLFQ 各级时间片，为空时取 quantum × 2^级
+    pub boost: Option<f64>,         // MLFQ 优先级提升周期
+    pub aging: Option<f64>,         // 优先级调度的老化周期
+    pub horizon: Option<f64>,       // 周期作业展开到该时刻，None 时取超周期（见 realtime.rs）
+    pub lottery_seed: u64,          // 彩票调度的抽签种子
+    pub sim: SimOptions,            // 与策略无关的引擎选项
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self { quantum: 2.0, switch_cost: 0.0, levels: 3, level_quanta: Vec::new(), boost: None, aging: None, horizon: None, lottery_seed: 0, sim: SimOptions::default() }
+    }
+}
+
+impl SchedulerConfig {
+    fn mlfq_quanta(&self) -> Vec<f64> {
+        if self.level_quanta.is_empty() {
+            (0..self.levels).map(|i| self.quantum * f64::powi(2.0, i as i32)).collect()
+        } else {
+            self.level_quanta.clone()
+        }
+    }
+}
+
+// 可选的调度算法（命令行中的名称）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Fcfs,
+    Sjf,
+    Hrrn,
+    Srtf,
+    RoundRobin,
+    Mlfq,
+    Priority,
+    PreemptivePriority,
+    Edf,
+    NpEdf,
+    RateMonotonic,
+    Lottery,
+    Stride,
+}
+
+impl Algorithm {
+    // compare / experiment 缺省比较的批处理算法（实时调度算法需用 -a 指定）
+    pub const ALL: [Algorithm; 8] = [
+        Algorithm::Fcfs,
+        Algorithm::Sjf,
+        Algorithm::Hrrn,
+        Algorithm::Srtf,
+        Algorithm::RoundRobin,
+        Algorithm::Mlfq,
+        Algorithm::Priority,
+        Algorithm::PreemptivePriority,
+    ];
+
+    pub fn parse(name: &str) -> Option<Algorithm> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "fcfs" => Some(Algorithm::Fcfs),
+            "sjf" => Some(Algorithm::Sjf),
+            "hrrn" => Some(Algorithm::Hrrn),
+            "srtf" => Some(Algorithm::Srtf),
+            "rr" => Some(Algorithm::RoundRobin),
+            "mlfq" => Some(Algorithm::Mlfq),
+            "prio" => Some(Algorithm::Priority),
+            "pprio" => Some(Algorithm::PreemptivePriority),
+            "edf" => Some(Algorithm::Edf),
+            "npedf" => Some(Algorithm::NpEdf),
+            "rm" => Some(Algorithm::RateMonotonic),
+            "lottery" => Some(Algorithm::Lottery),
+            "stride" => Some(Algorithm::Stride),
+            _ => None,
+        }
+    }
+
+    // 是否使用时间片
+    pub fn uses_quantum(self) -> bool {
+        matches!(self, Algorithm::RoundRobin | Algorithm::Mlfq | Algorithm::Lottery | Algorithm::Stride)
+    }
+
+    pub fn scheduler(self, config: &SchedulerConfig) -> Box<dyn Scheduler> {
+        match self {
+            Algorithm::Fcfs => Box::new(Fcfs),
+            Algorithm::Sjf => Box::new(Sjf),
+            Algorithm::Hrrn => Box::new(Hrrn),
+            Algorithm::Srtf => Box::new(Srtf),
+            Algorithm::RoundRobin => Box::new(RoundRobin { quantum: config.quantum, switch_cost: config.switch_cost }),
+            Algorithm::Mlfq => Box::new(Mlfq { quanta: config.mlfq_quanta(), boost: config.boost }),
+            Algorithm::Priority => Box::new(Priority { preemptive: false, aging: config.aging }),
+            Algorithm::PreemptivePriority => Box::new(Priority { preemptive: true, aging: config.aging }),
+            Algorithm::Edf => Box::new(Edf { preemptive: true }),
+            Algorithm::NpEdf => Box::new(Edf { preemptive: false }),
+            Algorithm::RateMonotonic => Box::new(RateMonotonic),
+            Algorithm::Lottery => Box::new(Lottery { quantum: config.quantum, switch_cost: config.switch_cost, seed: config.lottery_seed }),
+            Algorithm::Stride => Box::new(Stride { quantum: config.quantum, switch_cost: config.switch_cost }),
+        }
+    }
+}
+
+// 时间片轮转时剩余时间会累积浮点误差，小于该值视为已完成（刻度模式下时间都是整数，不受影响）
+const EPS: f64 = 1e-9;
+
+// 引擎的时间表示。Float 直接以浮点分钟计算；Ticks(n) 先把所有时刻换算成 1/n 分钟的整数刻度再调度，
+// 结束后换算回分钟。2^53 以内的整数在 f64 中加减、比较都是精确的，相等的响应比也得到完全相同的商，
+// 结果因此与手算逐位一致。作业时间必须落在刻度上，时间片、切换开销与定时周期按刻度取整
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeMode {
+    Float,
+    Ticks(u32),
+}
+
+// 调度引擎的选项：时间表示、取舍规则与主存限制（None 表示不考虑主存，到达即就绪）
+//
+// job_scheduler 给出时为两级调度的教材模型：simulate 的 m 是多道程序度而不是 CPU 数，
+// 作业调度用该算法从后备队列中调入作业，使系统中（就绪或运行）的作业至多 m 个，
+// 进程调度用传给 simulate 的策略在唯一的 CPU 上分时运行它们
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimOptions {
+    pub time: TimeMode,
+    pub tie: TieBreak,
+    pub channel_tie: ChannelTie,
+    pub memory: Option<MemoryModel>,
+    pub job_scheduler: Option<Algorithm>,
+}
+
+impl Default for SimOptions {
+    fn default() -> Self {
+        Self { time: TimeMode::Float, tie: TieBreak::Fifo, channel_tie: ChannelTie::Lowest, memory: None, job_scheduler: None }
+    }
+}
+
+impl SimOptions {
+    // m 对应的 CPU 数：两级调度时只有一个 CPU
+    pub fn cpus(&self, m: usize) -> usize {
+        if self.job_scheduler.is_some() { 1 } else { m }
+    }
+
+    // 结果中显示的算法名：两级调度时为"作业调度+进程调度"
+    pub fn label(&self, policy: &dyn Scheduler) -> String {
+        match self.job_scheduler {
+            Some(alg) => format!("{}+{}", alg.scheduler(&SchedulerConfig::default()).label(), policy.label()),
+            None => policy.label(),
+        }
+    }
+}
+
+// 在刻度模式下包装策略，把它给出的时间参数换算成刻度
+struct Ticked<'a> {
+    inner: &'a dyn Scheduler,
+    scale: f64,
+}
+
+impl Ticked<'_> {
+    // 策略参数已由 check_timings 确认落在刻度上，这里只消去乘法的舍入误差
+    fn tick(&self, t: f64) -> f64 {
+        (t * self.scale).round()
+    }
+}
+
+impl Scheduler for Ticked<'_> {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn select(&self, ready: &[Job], now: f64, tie: &mut TieBreaker) -> usize {
+        self.inner.select(ready, now, tie)
+    }
+
+    fn key(&self, job: &Job) -> Option<f64> {
+        self.inner.key(job)
+    }
+
+    fn keyed(&self) -> bool {
+        self.inner.keyed()
+    }
+
+    fn fifo_ties(&self) -> bool {
+        self.inner.fifo_ties()
+    }
+
+    fn criterion(&self) -> Option<(&'static str, bool)> {
+        self.inner.criterion()
+    }
+
+    // 关键字换算回分钟后再取值，讲解中的运行时间、截止时间等以分钟显示；没有关键字的策略的依据
+    // （响应比、彩票数）与时间单位无关，直接按刻度取值，与引擎比较时的取值完全相同
+    fn score(&self, job: &Job, now: f64) -> f64 {
+        if self.inner.keyed() {
+            self.inner.score(&from_ticks(job.clone(), self.scale), now / self.scale)
+        } else {
+            self.inner.score(job, now)
+        }
+    }
+
+    fn preemptive(&self) -> bool {
+        self.inner.preemptive()
+    }
+
+    fn quantum(&self, job: &Job) -> Option<f64> {
+        self.inner.quantum(job).map(|q| self.tick(q))
+    }
+
+    fn switch_cost(&self) -> f64 {
+        self.tick(self.inner.switch_cost())
+    }
+
+    fn period(&self) -> Option<f64> {
+        self.inner.period().map(|p| self.tick(p))
+    }
+
+    fn on_arrive(&self, job: &mut Job, now: f64) {
+        self.inner.on_arrive(job, now)
+    }
+
+    fn on_expire(&self, job: &mut Job, now: f64) {
+        self.inner.on_expire(job, now)
+    }
+
+    fn on_period(&self, job: &mut Job, now: f64, waited: bool) {
+        self.inner.on_period(job, now, waited)
+    }
+
+    fn on_join(&self, job: &mut Job, global: f64) {
+        self.inner.on_join(job, global)
+    }
+
+    fn proportional(&self) -> bool {
+        self.inner.proportional()
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+}
+
+// value 是 1/ticks 的整数倍（且换算后仍能精确表示）时给出刻度数
+fn on_tick(value: f64, ticks: u32) -> Option<f64> {
+    let scale = ticks as f64;
+    let t = (value * scale).round();
+    ((value * scale - t).abs() <= 1e-6 && t < (1u64 << 53) as f64).then_some(t)
+}
+
+// 刻度模式下策略的时间片、切换开销、定时周期不做舍入，不在刻度上时报错
+fn check_timings(policy: &dyn Scheduler, ticks: u32) -> Result<(), ScheduleError> {
+    match policy.timings().into_iter().find(|&(_, value)| on_tick(value, ticks).is_none()) {
+        Some((param, value)) => Err(ScheduleError::ParamOffTick { param, value, ticks }),
+        None => Ok(()),
+    }
+}
+
+fn to_ticks(job: &Job, ticks: u32) -> Result<Job, ScheduleError> {
+    let convert = |value: f64, field: &'static str| on_tick(value, ticks).ok_or(ScheduleError::OffTick { id: job.id, field, value, ticks });
+    let mut out = job.clone();
+    out.arrival = convert(job.arrival, "arrival")?;
+    out.service = convert(job.service, "service")?;
+    out.remaining = out.service;
+    out.deadline = job.deadline.map(|d| convert(d, "deadline")).transpose()?;
+    out.period = job.period.map(|p| convert(p, "period")).transpose()?;
+    Ok(out)
+}
+
+fn from_ticks(mut job: Job, scale: f64) -> Job {
+    job.arrival /= scale;
+    job.service /= scale;
+    job.remaining /= scale;
+    job.start = job.start.map(|t| t / scale);
+    job.end = job.end.map(|t| t / scale);
+    job.admitted = job.admitted.map(|t| t / scale);
+    job.enqueued /= scale;
+    job.deadline = job.deadline.map(|t| t / scale);
+    job.period = job.period.map(|t| t / scale);
+    for s in job.segments.iter_mut() {
+        s.start /= scale;
+        s.end /= scale;
+    }
+    for (t, _) in job.level_log.iter_mut() {
+        *t /= scale;
+    }
+    job
+}
+
+// 某条道上正在运行的作业，since 为当前执行段的开始时刻（已扣除切换开销），
+// slice_end 为时间片用完的时刻
+struct Running {
+    job: Job,
+    since: f64,
+    slice_end: Option<f64>,
+}
+
+impl Running {
+    // 按当前执行段不被打断计算的完成时刻
+    fn finish_at(&self) -> f64 {
+        self.since + self.job.remaining
+    }
+
+    // 下一个与该道相关的事件：完成或时间片用完
+    fn next_event(&self) -> f64 {
+        self.slice_end.map_or(self.finish_at(), |e| e.min(self.finish_at()))
+    }
+
+    // 当前时刻已运行的时间
+    fn elapsed(&self, now: f64) -> f64 {
+        (now - self.since).max(0.0)
+    }
+
+    // 结束当前执行段，扣除已运行的时间
+    fn close(mut self, channel: usize, now: f64) -> Job {
+        if now > self.since {
+            self.job.segments.push(Segment { channel, start: self.since, end: now });
+        }
+        self.job.remaining = (self.job.remaining - self.elapsed(now)).max(0.0);
+        self.job
+    }
+}
+
+// 分配到多道：返回各作业的 start/end/segments（按 id 排序）。m 为道数（CPU 数），opts 为时间表示、取舍规则与主存限制
+//
+// 有主存限制时为两级调度：到达的作业先进入后备队列，作业调度从能放进主存的作业中按同一策略挑选、
+// 分配分区后才进入就绪队列，由进程调度分派到道上；作业完成时释放分区，再从后备队列调入。
+// opts.job_scheduler 给出时作业调度改用该算法，并且 m 为多道程序度、只有一个 CPU（见 SimOptions）
+//
+// 事件驱动：每个事件时刻（到达、完成或时间片用完）先回收完成的作业、接纳新到达的作业，
+// 再把时间片用完的作业放回就绪队列末尾（排在同时刻到达的作业之后），然后处理定时事件，
+// 抢占式策略再把运行中的作业与就绪作业一起重新挑选，最后由策略为空闲道挑选作业；
+// 之后把时间推进到下一个事件
+//
+// 各道的完成/时间片事件放在堆中，空闲道按编号放在有序集合中，就绪队列在策略给出关键字时也是堆
+// （见 queue.rs），每个事件的代价为 O(log n)，百万作业、数百道也能在数秒内算完。
+// HRRN、随机取舍需要逐个比较就绪作业，随机分派道需要每个时刻重排全部道，这些情况仍是线性的
+pub fn simulate(jobs: &JobStream, m: usize, policy: &dyn Scheduler, opts: SimOptions) -> Result<Vec<Job>, ScheduleError> {
+    simulate_with(jobs, m, policy, opts, None)
+}
+
+// 同 simulate，并把每个调度决策记入 trace（见 trace.rs）
+pub fn simulate_traced(
+    jobs: &JobStream,
+    m: usize,
+    policy: &dyn Scheduler,
+    opts: SimOptions,
+    trace: &mut Trace,
+) -> Result<Vec<Job>, ScheduleError> {
+    simulate_with(jobs, m, policy, opts, Some(trace))
+}
+
+fn simulate_with(
+    jobs: &JobStream,
+    m: usize,
+    policy: &dyn Scheduler,
+    opts: SimOptions,
+    mut trace: Option<&mut Trace>,
+) -> Result<Vec<Job>, ScheduleError> {
+    if m == 0 {
+        return Err(ScheduleError::NoChannels);
+    }
+    if let Some(model) = opts.memory {
+        if let Some(j) = jobs.jobs().iter().find(|j| j.memory > model.capacity) {
+            return Err(ScheduleError::MemoryExceeded { id: j.id, memory: j.memory, capacity: model.capacity });
+        }
+    }
+    let mut tie = TieBreaker::new(opts.tie, opts.channel_tie);
+    let scale = match opts.time {
+        TimeMode::Float => 1.0,
+        TimeMode::Ticks(ticks) => ticks as f64,
+    };
+    // 作业调度策略在刻度模式下同样包装，事件日志中的挑选依据才以分钟计
+    let job_policy = opts.job_scheduler.map(|alg| alg.scheduler(&SchedulerConfig::default()));
+    let job_ticked = job_policy.as_deref().map(|inner| Ticked { inner, scale });
+    let mut admission = Admission {
+        policy: match opts.time {
+            TimeMode::Float => job_policy.as_deref(),
+            TimeMode::Ticks(_) => job_ticked.as_ref().map(|p| p as &dyn Scheduler),
+        },
+        degree: opts.job_scheduler.map(|_| m),
+        memory: opts.memory.map(Memory::new),
+    };
+    let cpus = opts.cpus(m);
+    if let Some(t) = trace.as_deref_mut() {
+        t.start(cpus, scale);
+    }
+    match opts.time {
+        TimeMode::Float => Ok(run(jobs.jobs().to_vec(), cpus, policy, &mut tie, &mut admission, trace)),
+        TimeMode::Ticks(ticks) => {
+            check_timings(policy, ticks)?;
+            let all = jobs.jobs().iter().map(|j| to_ticks(j, ticks)).collect::<Result<Vec<Job>, _>>()?;
+            let policy = Ticked { inner: policy, scale };
+            Ok(run(all, cpus, &policy, &mut tie, &mut admission, trace)
+                .into_iter()
+                .map(|j| from_ticks(j, scale).with_grid(ticks))
+                .collect())
+        }
+    }
+}
+
+// 作业调度的约束：挑选用的策略（None 时沿用进程调度策略）、多道程序度与主存
+struct Admission<'a> {
+    policy: Option<&'a dyn Scheduler>,
+    degree: Option<usize>,
+    memory: Option<Memory>,
+}
+
+impl Admission<'_> {
+    fn unconstrained(&self) -> bool {
+        self.degree.is_none() && self.memory.is_none()
+    }
+
+    fn fits(&self, job: &Job) -> bool {
+        job.memory == 0 || self.memory.as_ref().is_none_or(|mem| mem.fits(job.memory))
+    }
+}
+
+// 各道的状态。道上换上或取下作业时该道的代号加 1，事件堆中代号过期的事件直接丢弃（惰性删除）
+struct Channels {
+    slots: Vec<Option<Running>>,
+    free: BTreeSet<usize>,              // 空闲道，按编号排列
+    events: BinaryHeap<Reverse<Event>>, // 各道的下一个完成或时间片用完事件
+    generation: Vec<u64>,
+    busy: usize,
+}
+
+// 某道在 at 时刻的事件
+#[derive(Clone, Copy, Debug)]
+struct Event {
+    at: f64,
+    channel: usize,
+    generation: u64,
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.at.total_cmp(&other.at).then(self.channel.cmp(&other.channel))
+    }
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Event {}
+
+impl Channels {
+    fn new(m: usize) -> Self {
+        Channels {
+            slots: (0..m).map(|_| None).collect(),
+            free: (0..m).collect(),
+            events: BinaryHeap::new(),
+            generation: vec![0; m],
+            busy: 0,
+        }
+    }
+
+    fn put(&mut self, k: usize, running: Running) {
+        self.generation[k] += 1;
+        self.events.push(Reverse(Event { at: running.next_event(), channel: k, generation: self.generation[k] }));
+        self.free.remove(&k);
+        self.busy += 1;
+        self.slots[k] = Some(running);
+    }
+
+    fn take(&mut self, k: usize) -> Running {
+        self.generation[k] += 1;
+        self.free.insert(k);
+        self.busy -= 1;
+        self.slots[k].take().unwrap()
+    }
+
+    fn live(&self, e: &Event) -> bool {
+        e.generation == self.generation[e.channel]
+    }
+
+    // 事件时刻不晚于 until 的道（按编号排列），其事件从堆中取出
+    fn due(&mut self, until: f64) -> Vec<usize> {
+        let mut due = Vec::new();
+        while let Some(&Reverse(e)) = self.events.peek() {
+            if e.at > until {
+                break;
+            }
+            self.events.pop();
+            if self.live(&e) {
+                due.push(e.channel);
+            }
+        }
+        due.sort_unstable();
+        due
+    }
+
+    // 事件未到的道把事件放回堆中
+    fn defer(&mut self, k: usize) {
+        let at = self.slots[k].as_ref().unwrap().next_event();
+        self.events.push(Reverse(Event { at, channel: k, generation: self.generation[k] }));
+    }
+
+    // 最早的道事件时刻
+    fn next_event(&mut self) -> Option<f64> {
+        while let Some(&Reverse(e)) = self.events.peek() {
+            if self.live(&e) {
+                return Some(e.at);
+            }
+            self.events.pop();
+        }
+        None
+    }
+}
+
+// 记一条事件（trace 为 None 时不做任何事），附带事件发生后的就绪队列
+fn note(trace: &mut Option<&mut Trace>, time: f64, kind: EventKind, job: Option<usize>, channel: Option<usize>, ready: &ReadyQueue) {
+    if let Some(t) = trace {
+        t.record(time, kind, job, channel, ready.ids());
+    }
+}
+
+// 挑选前队列中满足 accept 的候选：已等待时间（到达以来未运行的时间）与队列所用策略的挑选依据取值
+fn candidates(queue: &ReadyQueue, now: f64, accept: impl Fn(&Job) -> bool) -> Vec<Candidate> {
+    let policy = queue.policy();
+    let candidate = |j: &Job| Candidate {
+        job: j.id,
+        arrival: j.arrival,
+        service: j.service,
+        wait: now - j.arrival - (j.service - j.remaining),
+        value: policy.score(j, now),
+    };
+    queue.queued().into_iter().filter(|j| accept(j)).map(candidate).collect()
+}
+
+fn run(
+    mut all: Vec<Job>,
+    m: usize,
+    policy: &dyn Scheduler,
+    tie: &mut TieBreaker,
+    admission: &mut Admission,
+    mut trace: Option<&mut Trace>,
+) -> Vec<Job> {
+    let n = all.len();
+    // 按到达时间排序用于发现新到达（稳定排序，同时到达者保持输入顺序）
+    all.sort_by(|a, b| a.arrival.total_cmp(&b.arrival));
+    let mut arrivals = all.into_iter().peekable();
+
+    let mut time = 0.0f64;
+    let mut finished: Vec<Job> = Vec::with_capacity(n);
+    let mut ready = ReadyQueue::new(policy, tie.rule());
+    // 已到达、等待调入的作业（后备队列），只在有作业调度约束时使用
+    let mut backlog = ReadyQueue::new(admission.policy.unwrap_or(policy), tie.rule());
+    let mut channels = Channels::new(m);
+    let mut last_job: Vec<Option<usize>> = vec![None; m]; // 每条道上一次运行的作业，用于计算切换开销
+    let period = policy.period();
+    let mut next_tick = period.unwrap_or(f64::INFINITY); // 下一个定时事件
+
+    while finished.len() < n {
+        // 回收已完成的作业；到期但只是时间片用完的道留到接纳新作业之后处理
+        let mut expired = Vec::new();
+        for k in channels.due(time + EPS) {
+            let r = channels.slots[k].as_ref().unwrap();
+            if r.finish_at() <= time + EPS {
+                let mut job = channels.take(k).close(k, time);
+                job.end = Some(time);
+                note(&mut trace, time, EventKind::Complete, Some(job.id), Some(k), &ready);
+                if let (Some(mem), Some(address)) = (admission.memory.as_mut(), job.address) {
+                    mem.free(address, job.memory);
+                }
+                finished.push(job);
+            } else if r.slice_end.is_some_and(|e| e <= time) {
+                expired.push(k);
+            } else {
+                channels.defer(k);
+            }
+        }
+
+        // 已到达的作业进入后备队列，再由作业调度调入、加入 ready；
+        // fresh 记录本时刻是否有作业新进入就绪队列（抢占只可能由它们引起）
+        let mut fresh = false;
+        while let Some(job) = arrivals.next_if(|j| j.arrival <= time) {
+            note(&mut trace, time, EventKind::Arrive, Some(job.id), None, &ready);
+            if admission.unconstrained() {
+                let id = job.id;
+                ready.enter(job, time);
+                note(&mut trace, time, EventKind::Ready, Some(id), None, &ready);
+                fresh = true;
+            } else {
+                backlog.push(job, time);
+            }
+        }
+        if !admission.unconstrained() {
+            fresh |= admit(&mut backlog, &mut ready, channels.busy, admission, time, tie, &mut trace);
+        }
+
+        // 时间片用完的作业回到就绪队列末尾
+        for k in expired {
+            let mut job = channels.take(k).close(k, time);
+            let id = job.id;
+            note(&mut trace, time, EventKind::Expire, Some(id), Some(k), &ready);
+            policy.on_expire(&mut job, time);
+            ready.push(job, time);
+            note(&mut trace, time, EventKind::Ready, Some(id), None, &ready);
+            fresh = true;
+        }
+
+        // 定时事件作用于所有就绪和运行中的作业；系统空闲期间错过的周期直接跳过
+        if let Some(p) = period {
+            if next_tick <= time {
+                ready.for_each_mut(|job| policy.on_period(job, time, time - job.enqueued >= p - EPS));
+                for r in channels.slots.iter_mut().flatten() {
+                    policy.on_period(&mut r.job, time, false);
+                }
+                while next_tick <= time {
+                    next_tick += p;
+                }
+                note(&mut trace, time, EventKind::Period, None, None, &ready);
+                fresh = true;
+            }
+        }
+
+        if policy.preemptive() && !ready.is_empty() {
+            if ready.keyed() {
+                if fresh {
+                    preempt_keyed(&mut channels, &mut ready, time, policy, &mut trace);
+                }
+            } else {
+                preempt(&mut channels, &mut ready, time, policy, tie, &mut trace);
+            }
+        }
+
+        // 依次为空闲道挑选作业
+        let mut order: Vec<usize> = channels.free.iter().copied().collect();
+        if !ready.is_empty() {
+            tie.channel_order(&mut order);
+        }
+        for k in order {
+            if ready.is_empty() {
+                break;
+            }
+            if channels.slots[k].is_some() {
+                continue;
+            }
+            let candidates = trace.is_some().then(|| candidates(&ready, time, |_| true));
+            let mut job = ready.pop(time, tie).unwrap();
+            // 该道上一次运行的是别的作业时需要付出切换开销（每道第一次分派不计）
+            let cost = if last_job[k].is_some_and(|id| id != job.id) { policy.switch_cost() } else { 0.0 };
+            let since = time + cost;
+            last_job[k] = Some(job.id);
+            job.start.get_or_insert(since);
+            let slice_end = policy.quantum(&job).map(|q| since + q);
+            if let (Some(t), Some(candidates)) = (trace.as_deref_mut(), candidates) {
+                t.choice(time, EventKind::Dispatch, job.id, Some(k), ready.ids(), candidates);
+            }
+            channels.put(k, Running { job, since, slice_end });
+        }
+        if let Some(t) = trace.as_deref_mut() {
+            t.channels(time, channels.slots.iter().map(Option::is_some), &ready.ids());
+        }
+
+        // 推进到下一个事件：下一个到达或最早的完成/时间片用完
+        let next_arrival = arrivals.peek().map(|j| j.arrival);
+        let mut next_finish = channels.next_event();
+        if !ready.is_empty() || next_finish.is_some() {
+            next_finish = Some(next_finish.map_or(next_tick, |t| t.min(next_tick)));
+        }
+        time = match (next_arrival, next_finish) {
+            (Some(na), Some(nf)) => na.min(nf),
+            (Some(na), None) => na,
+            (None, Some(nf)) => nf,
+            (None, None) => break,
+        };
+    }
+
+    finished.sort_by_key(|j| j.id);
+    finished
+}
+
+// 作业调度：在多道程序度未满时反复从后备队列中放得下的作业里按作业调度策略挑选一个，
+// 分配分区后加入就绪队列。系统中已调入、尚未完成的作业为 ready 与运行中的 running 个，
+// 不占主存的作业不分配分区。返回是否调入了作业
+fn admit(
+    backlog: &mut ReadyQueue,
+    ready: &mut ReadyQueue,
+    running: usize,
+    admission: &mut Admission,
+    now: f64,
+    tie: &mut TieBreaker,
+    trace: &mut Option<&mut Trace>,
+) -> bool {
+    let mut admitted = false;
+    while admission.degree.is_none_or(|d| ready.len() + running < d) {
+        let candidates = trace.is_some().then(|| candidates(backlog, now, |j| admission.fits(j)));
+        let next = match &admission.memory {
+            Some(_) => backlog.pop_where(now, tie, |j| admission.fits(j)),
+            None => backlog.pop(now, tie),
+        };
+        let Some(mut job) = next else { break };
+        if let (Some(mem), true) = (admission.memory.as_mut(), job.memory > 0) {
+            job.address = mem.allocate(job.memory);
+        }
+        job.admitted = Some(now);
+        let id = job.id;
+        if let (Some(t), Some(candidates)) = (trace.as_deref_mut(), candidates) {
+            t.choice(now, EventKind::Admit, id, None, ready.ids(), candidates);
+        }
+        ready.enter(job, now);
+        note(trace, now, EventKind::Ready, Some(id), None, ready);
+        admitted = true;
+    }
+    admitted
+}
+
+// 抢占：运行中的作业（剩余时间折算到当前时刻）排在候选最前面，使其在相等时保留道，
+// 之后是就绪队列；按策略依次挑出 m 个，未被挑中的运行作业被抢占
+fn preempt(
+    channels: &mut Channels,
+    ready: &mut ReadyQueue,
+    now: f64,
+    policy: &dyn Scheduler,
+    tie: &mut TieBreaker,
+    trace: &mut Option<&mut Trace>,
+) {
+    let mut pool: Vec<Job> = Vec::new();
+    let mut origin: Vec<Option<usize>> = Vec::new(); // 候选来自哪条道，None 表示就绪队列
+    for (k, slot) in channels.slots.iter().enumerate() {
+        if let Some(r) = slot {
+            let mut view = r.job.clone();
+            view.remaining -= r.elapsed(now);
+            pool.push(view);
+            origin.push(Some(k));
+        }
+    }
+    pool.extend(ready.jobs().iter().cloned());
+    origin.extend(std::iter::repeat_n(None, ready.len()));
+
+    let m = channels.slots.len();
+    let mut keep = vec![false; m];
+    for _ in 0..m {
+        if pool.is_empty() {
+            break;
+        }
+        tie.keep = origin.iter().take_while(|o| o.is_some()).count();
+        let i = policy.select(&pool, now, tie);
+        if let Some(k) = origin[i] {
+            keep[k] = true;
+        }
+        pool.remove(i);
+        origin.remove(i);
+    }
+
+    tie.keep = 0;
+    for (k, kept) in keep.into_iter().enumerate() {
+        if channels.slots[k].is_some() && !kept {
+            displace(channels, ready, k, now, trace);
+        }
+    }
+}
+
+// 按关键字维护就绪队列时的抢占，与 preempt 挑出的结果相同：m 个名额中空闲道先由最好的就绪作业占去，
+// 之后第 t 个就绪作业（按关键字）严格好于第 t 差的运行作业时，后者被抢占（相等时运行作业保留道）。
+// 只在有作业新进入就绪队列时调用——其余时刻运行作业都不差于任何就绪作业
+fn preempt_keyed(channels: &mut Channels, ready: &mut ReadyQueue, now: f64, policy: &dyn Scheduler, trace: &mut Option<&mut Trace>) {
+    let idle = channels.slots.len() - channels.busy;
+    if ready.len() <= idle {
+        return;
+    }
+    // 运行作业的关键字（剩余时间折算到当前时刻），从最差的排起；关键字相等时编号大的道更差
+    let mut running: Vec<(f64, usize)> = Vec::with_capacity(channels.busy);
+    for (k, slot) in channels.slots.iter_mut().enumerate() {
+        if let Some(r) = slot {
+            let remaining = r.job.remaining;
+            r.job.remaining -= r.elapsed(now);
+            running.push((policy.key(&r.job).unwrap_or(0.0) + 0.0, k));
+            r.job.remaining = remaining;
+        }
+    }
+    let worst = running.iter().copied().max_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    let mut first = None;
+    ready.scan(|i, key| {
+        first = Some(key);
+        i < idle
+    });
+    if !worst.zip(first).is_some_and(|((w, _), key)| key < w) {
+        return;
+    }
+    running.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.1.cmp(&a.1)));
+    let mut displaced = Vec::new();
+    ready.scan(|i, key| {
+        if i < idle {
+            return true;
+        }
+        match running.get(i - idle) {
+            Some(&(w, k)) if key < w => {
+                displaced.push(k);
+                true
+            }
+            _ => false,
+        }
+    });
+    displaced.sort_unstable();
+    for k in displaced {
+        displace(channels, ready, k, now, trace);
+    }
+}
+
+// 道 k 上的作业被抢占，回到就绪队列
+fn displace(channels: &mut Channels, ready: &mut ReadyQueue, k: usize, now: f64, trace: &mut Option<&mut Trace>) {
+    let job = channels.take(k).close(k, now);
+    let id = job.id;
+    note(trace, now, EventKind::Preempt, Some(id), Some(k), ready);
+    ready.push(job, now);
+    note(trace, now, EventKind::Ready, Some(id), None, ready);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{self, ArrivalDist, ServiceDist, WorkloadSpec};
+    use crate::result::ScheduleResult;
+
+    // 去掉关键字的包装：就绪队列改用数组，抢占走 preempt 而不是 preempt_keyed
+    struct Unkeyed<'a>(&'a dyn Scheduler);
+
+    impl Scheduler for Unkeyed<'_> {
+        fn name(&self) -> &'static str {
+            self.0.name()
+        }
+
+        fn select(&self, ready: &[Job], now: f64, tie: &mut TieBreaker) -> usize {
+            self.0.select(ready, now, tie)
+        }
+
+        fn preemptive(&self) -> bool {
+            self.0.preemptive()
+        }
+
+        fn period(&self) -> Option<f64> {
+            self.0.period()
+        }
+
+        fn on_period(&self, job: &mut Job, now: f64, waited: bool) {
+            self.0.on_period(job, now, waited)
+        }
+    }
+
+    // 教材样例 B（lab1 demo 中的作业流 B）
+    fn sample_b() -> JobStream {
+        let jobs = [(1, 0.0, 8.0), (2, 1.0, 4.0), (3, 2.0, 9.0), (4, 3.0, 5.0), (5, 10.0, 2.0), (6, 10.0, 1.0)];
+        JobStream::new(jobs.iter().map(|&(id, a, s)| Job::new(id, a, s)).collect()).unwrap()
+    }
+
+    // 整数时间的随机作业流，关键字相等的情况很多；带优先数与截止时间
+    fn workload(seed: u64) -> JobStream {
+        let spec = WorkloadSpec {
+            jobs: 40,
+            seed,
+            arrival: ArrivalDist::Exponential { mean: 1.0 },
+            service: ServiceDist::Uniform { min: 1.0, max: 6.0 },
+            decimals: 0,
+        };
+        let jobs = generator::generate(&spec)
+            .into_iter()
+            .map(|j| {
+                let (priority, deadline) = ((j.id * 7 % 5) as i64, j.arrival + j.service * (1 + j.id % 3) as f64);
+                j.with_priority(priority).with_deadline(Some(deadline))
+            })
+            .collect();
+        JobStream::new(jobs).unwrap()
+    }
+
+    // 各作业的 (开始, 结束)
+    type Spans = [(f64, f64); 6];
+
+    fn spans(jobs: &[Job]) -> Vec<(usize, Option<f64>, Option<f64>)> {
+        jobs.iter().map(|j| (j.id, j.start, j.end)).collect()
+    }
+
+    #[test]
+    fn pinned_results_on_sample_b() {
+        let cases: [(&dyn Scheduler, usize, Spans); 8] = [
+            (&Fcfs, 1, [(0.0, 8.0), (8.0, 12.0), (12.0, 21.0), (21.0, 26.0), (26.0, 28.0), (28.0, 29.0)]),
+            (&Fcfs, 2, [(0.0, 8.0), (1.0, 5.0), (5.0, 14.0), (8.0, 13.0), (13.0, 15.0), (14.0, 15.0)]),
+            (&Sjf, 1, [(0.0, 8.0), (8.0, 12.0), (20.0, 29.0), (15.0, 20.0), (13.0, 15.0), (12.0, 13.0)]),
+            (&Sjf, 2, [(0.0, 8.0), (1.0, 5.0), (8.0, 17.0), (5.0, 10.0), (11.0, 13.0), (10.0, 11.0)]),
+            (&Hrrn, 1, [(0.0, 8.0), (8.0, 12.0), (20.0, 29.0), (13.0, 18.0), (18.0, 20.0), (12.0, 13.0)]),
+            (&Hrrn, 2, [(0.0, 8.0), (1.0, 5.0), (8.0, 17.0), (5.0, 10.0), (10.0, 12.0), (12.0, 13.0)]),
+            (&Srtf, 1, [(0.0, 20.0), (1.0, 5.0), (20.0, 29.0), (5.0, 10.0), (11.0, 13.0), (10.0, 11.0)]),
+            (&Srtf, 2, [(0.0, 8.0), (1.0, 5.0), (8.0, 18.0), (5.0, 10.0), (10.0, 12.0), (10.0, 11.0)]),
+        ];
+        for (policy, m, expected) in cases {
+            let done = simulate(&sample_b(), m, policy, SimOptions::default()).unwrap();
+            let got: Vec<(f64, f64)> = done.iter().map(|j| (j.start.unwrap(), j.end.unwrap())).collect();
+            assert_eq!(got, expected, "{} m={}", policy.name(), m);
+        }
+    }
+
+    #[test]
+    fn tick_mode_exports_exact_values() {
+        let rr = RoundRobin { quantum: 0.3, switch_cost: 0.0 };
+        let opts = SimOptions { time: TimeMode::Ticks(10), ..SimOptions::default() };
+        let done = simulate(&sample_b(), 1, &rr, opts).unwrap();
+        let result = ScheduleResult::new("RR(q=0.3)", 1, &done);
+        assert_eq!(
+            result.jobs_csv(true),
+            "algorithm,m,id,arrival,service,priority,start,end,turnaround,weighted_turnaround,waiting,response\n\
+             RR(q=0.3),1,1,0,8,0,0,26.3,26.3,3.2875,18.3,0\n\
+             RR(q=0.3),1,2,1,4,0,1.2,18.5,17.5,4.375,13.5,0.2\n\
+             RR(q=0.3),1,3,2,9,0,2.4,29,27,3,18,0.4\n\
+             RR(q=0.3),1,4,3,5,0,3.6,24.3,21.3,4.26,16.3,0.6\n\
+             RR(q=0.3),1,5,10,2,0,11.1,20.8,10.8,5.4,8.8,1.1\n\
+             RR(q=0.3),1,6,10,1,0,11.4,16.9,6.9,6.9,5.9,1.4\n"
+        );
+        let m = &result.metrics;
+        assert_eq!((m.avg_turnaround, m.avg_waiting, m.avg_response), (Some(18.3), Some(13.466666666666667), Some(0.6166666666666667)));
+    }
+
+    #[test]
+    fn keyed_queue_matches_list_queue() {
+        let pprio = Priority { preemptive: true, aging: Some(2.0) };
+        let prio = Priority { preemptive: false, aging: Some(2.0) };
+        let edf = Edf { preemptive: true };
+        let policies: [&dyn Scheduler; 6] = [&Fcfs, &Sjf, &Srtf, &pprio, &prio, &edf];
+        for seed in 1..=10 {
+            let jobs = workload(seed);
+            for policy in policies {
+                for tie in [TieBreak::Fifo, TieBreak::LowerId, TieBreak::EarlierArrival] {
+                    for m in 1..=3 {
+                        let opts = SimOptions { tie, ..SimOptions::default() };
+                        let keyed = simulate(&jobs, m, policy, opts).unwrap();
+                        let listed = simulate(&jobs, m, &Unkeyed(policy), opts).unwrap();
+                        assert_eq!(spans(&keyed), spans(&listed), "{} seed={} tie={:?} m={}", policy.name(), seed, tie, m);
+                        let segments = |jobs: &[Job]| jobs.iter().map(|j| j.segments.clone()).collect::<Vec<_>>();
+                        assert_eq!(segments(&keyed), segments(&listed), "{} seed={} tie={:?} m={}", policy.name(), seed, tie, m);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// 比例份额：把调度结果按时间分段，比较各作业实际得到的 CPU 份额与按彩票数应得的份额
+//
+// 实得份额 = 作业在该段内运行的时间 / 该段内 CPU 运行作业的总时间；
+// 应得份额 = 该段内作业在系统中（已到达、未完成）的各时刻按彩票数应分得的道数占当时分出的道数的比例，按时间平均。
+// 单道时即彩票数占当时在系统中的作业彩票总数的比例；多道时一个作业至多占一条道，m 条道按彩票数
+// 分给在系统中的作业，超过一条的部分按彩票数再分给其余作业（在系统中的作业不多于道数时各占一条，与彩票数无关）。
+// 彩票调度的实得份额随时间推移趋于应得份额，步幅调度在每一段内都接近应得份额
+
+use crate::job::Job;
+
+// 各作业在一段时间内的实得与应得份额
+pub struct Window {
+    pub start: f64,
+    pub end: f64,
+    pub shares: Vec<Share>, // 按作业 id 排序，只含该段内在系统中的作业
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Share {
+    pub job: usize,
+    pub tickets: u64,
+    pub got: f64,
+    pub entitled: f64,
+}
+
+impl Window {
+    // 实得份额与应得份额之差的最大绝对值
+    pub fn max_deviation(&self) -> f64 {
+        self.shares.iter().map(|s| (s.got - s.entitled).abs()).fold(0.0, f64::max)
+    }
+}
+
+// 把 [0, makespan) 等分成 count 段逐段统计，最后附上全程一段；m 为道数
+pub fn windows(jobs: &[Job], m: usize, count: usize) -> Vec<Window> {
+    let makespan = jobs.iter().filter_map(|j| j.end).fold(0.0, f64::max);
+    if makespan <= 0.0 || count == 0 {
+        return Vec::new();
+    }
+    let mut jobs: Vec<&Job> = jobs.iter().collect();
+    jobs.sort_by_key(|j| j.id);
+    let step = makespan / count as f64;
+    let mut out: Vec<Window> = (0..count)
+        .map(|i| {
+            let end = if i + 1 == count { makespan } else { step * (i + 1) as f64 };
+            window(&jobs, m, step * i as f64, end)
+        })
+        .collect();
+    out.push(window(&jobs, m, 0.0, makespan));
+    out
+}
+
+// m 条道按彩票数分给各作业，每个作业至多一条：分得超过一条的作业取一条，其余的道再按彩票数分给其他作业
+fn allocate(tickets: &[u64], m: usize) -> Vec<f64> {
+    let mut alloc = vec![0.0; tickets.len()];
+    let mut open: Vec<usize> = (0..tickets.len()).collect();
+    let mut channels = m as f64;
+    loop {
+        let total: u64 = open.iter().map(|&k| tickets[k]).sum();
+        let (capped, rest): (Vec<usize>, Vec<usize>) =
+            open.iter().partition(|&&k| channels * tickets[k] as f64 / total as f64 >= 1.0);
+        if capped.is_empty() || rest.is_empty() {
+            for &k in &open {
+                alloc[k] = (channels * tickets[k] as f64 / total as f64).min(1.0);
+            }
+            return alloc;
+        }
+        for &k in &capped {
+            alloc[k] = 1.0;
+        }
+        channels -= capped.len() as f64;
+        open = rest;
+    }
+}
+
+fn window(jobs: &[&Job], m: usize, start: f64, end: f64) -> Window {
+    // 作业在系统中的区间 [arrival, end)
+    let present = |j: &Job, t0: f64, t1: f64| j.arrival <= t0 && j.end.is_some_and(|e| e >= t1);
+    let ran = |j: &Job| j.segments.iter().map(|s| (s.end.min(end) - s.start.max(start)).max(0.0)).sum::<f64>();
+    let total: f64 = jobs.iter().map(|j| ran(j)).sum();
+
+    // 在该段内按到达、完成时刻切开，每一小段中在系统中的作业集合不变
+    let mut cuts: Vec<f64> = vec![start, end];
+    for j in jobs {
+        for t in [Some(j.arrival), j.end].into_iter().flatten() {
+            if t > start && t < end {
+                cuts.push(t);
+            }
+        }
+    }
+    cuts.sort_by(f64::total_cmp);
+    cuts.dedup();
+    let mut entitled = vec![0.0; jobs.len()];
+    let mut busy = 0.0;
+    for w in cuts.windows(2) {
+        let (t0, t1) = (w[0], w[1]);
+        let here: Vec<usize> = (0..jobs.len()).filter(|&k| present(jobs[k], t0, t1)).collect();
+        if here.is_empty() {
+            continue;
+        }
+        busy += t1 - t0;
+        let alloc = allocate(&here.iter().map(|&k| jobs[k].tickets).collect::<Vec<_>>(), m);
+        let used: f64 = alloc.iter().sum();
+        for (&k, a) in here.iter().zip(alloc) {
+            entitled[k] += (t1 - t0) * a / used;
+        }
+    }
+
+    let shares = jobs
+        .iter()
+        .zip(entitled)
+        .filter(|(j, e)| *e > 0.0 || ran(j) > 0.0)
+        .map(|(j, e)| Share {
+            job: j.id,
+            tickets: j.tickets,
+            got: if total > 0.0 { ran(j) / total } else { 0.0 },
+            entitled: if busy > 0.0 { e / busy } else { 0.0 },
+        })
+        .collect();
+    Window { start, end, shares }
+}
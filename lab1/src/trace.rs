@@ -0,0 +1,141 @@
+// 调度过程的事件日志：引擎在每个调度决策处记一条，附带当时就绪队列中的作业，
+// 用于逐步核对手算过程；导出为 JSON lines，每行一个事件
+
+use crate::json::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Arrive,   // 作业到达
+    Admit,    // 作业调度把作业调入（只在有主存或多道程序度限制时出现）
+    Ready,    // 进入就绪队列：到达、调入、时间片用完或被抢占之后
+    Dispatch, // 分派到道上
+    Expire,   // 时间片用完，离开道
+    Preempt,  // 被抢占，离开道
+    Complete, // 完成
+    Period,   // 定时事件（MLFQ 优先级提升、优先级老化）
+    Busy,     // 道由空闲转为忙
+    Idle,     // 道由忙转为空闲
+}
+
+impl EventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventKind::Arrive => "arrive",
+            EventKind::Admit => "admit",
+            EventKind::Ready => "ready",
+            EventKind::Dispatch => "dispatch",
+            EventKind::Expire => "expire",
+            EventKind::Preempt => "preempt",
+            EventKind::Complete => "complete",
+            EventKind::Period => "period",
+            EventKind::Busy => "busy",
+            EventKind::Idle => "idle",
+        }
+    }
+}
+
+// 分派时的一个候选作业：到达时间、运行时间、已等待的时间与策略的挑选依据取值（见 Scheduler::score）
+#[derive(Clone, Copy, Debug)]
+pub struct Candidate {
+    pub job: usize,
+    pub arrival: f64,
+    pub service: f64,
+    pub wait: f64,
+    pub value: f64,
+}
+
+// 一条事件；ready 为事件发生后就绪队列中的作业 id（按挑选顺序；关键字随时间变化的策略按进入顺序），
+// candidates 只用于分派与调入事件，为挑选前就绪队列（调入时为后备队列中放得下）的全部作业（含被选中者）
+#[derive(Clone, Debug)]
+pub struct TraceEvent {
+    pub time: f64,
+    pub kind: EventKind,
+    pub job: Option<usize>,
+    pub channel: Option<usize>,
+    pub ready: Vec<usize>,
+    pub candidates: Vec<Candidate>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    pub events: Vec<TraceEvent>,
+    scale: f64,      // 引擎时间 / scale = 分钟（刻度模式下为刻度数）
+    busy: Vec<bool>, // 各道上一次记录时是否忙
+}
+
+impl Trace {
+    pub fn new() -> Self {
+        Trace { events: Vec::new(), scale: 1.0, busy: Vec::new() }
+    }
+
+    // 引擎开始调度时调用：m 条道，时间单位为 1/scale 分钟
+    pub fn start(&mut self, m: usize, scale: f64) {
+        self.events.clear();
+        self.scale = scale;
+        self.busy = vec![false; m];
+    }
+
+    pub fn record(&mut self, time: f64, kind: EventKind, job: Option<usize>, channel: Option<usize>, ready: Vec<usize>) {
+        self.events.push(TraceEvent { time: time / self.scale, kind, job, channel, ready, candidates: Vec::new() });
+    }
+
+    // 分派或调入事件，附带挑选时的候选（时间与 time 同一单位）
+    pub fn choice(
+        &mut self,
+        time: f64,
+        kind: EventKind,
+        job: usize,
+        channel: Option<usize>,
+        ready: Vec<usize>,
+        mut candidates: Vec<Candidate>,
+    ) {
+        for c in candidates.iter_mut() {
+            c.arrival /= self.scale;
+            c.service /= self.scale;
+            c.wait /= self.scale;
+        }
+        self.record(time, kind, Some(job), channel, ready);
+        self.events.last_mut().unwrap().candidates = candidates;
+    }
+
+    // 每个事件时刻处理完后调用，记下各道忙/闲的变化
+    pub fn channels(&mut self, time: f64, busy: impl Iterator<Item = bool>, ready: &[usize]) {
+        let now: Vec<bool> = busy.collect();
+        for (k, &busy) in now.iter().enumerate() {
+            if busy != self.busy[k] {
+                let kind = if busy { EventKind::Busy } else { EventKind::Idle };
+                self.record(time, kind, None, Some(k), ready.to_vec());
+            }
+        }
+        self.busy = now;
+    }
+
+    pub fn to_json_lines(&self) -> String {
+        let mut out = String::new();
+        for e in &self.events {
+            let mut fields = vec![("time".into(), e.time.into()), ("event".into(), e.kind.as_str().into())];
+            if let Some(id) = e.job {
+                fields.push(("job".into(), id.into()));
+            }
+            if let Some(k) = e.channel {
+                fields.push(("channel".into(), k.into()));
+            }
+            fields.push(("ready".into(), Value::Arr(e.ready.iter().map(|&id| id.into()).collect())));
+            if !e.candidates.is_empty() {
+                let candidates = e.candidates.iter().map(|c| {
+                    Value::Obj(vec![
+                        ("job".into(), c.job.into()),
+                        ("arrival".into(), c.arrival.into()),
+                        ("service".into(), c.service.into()),
+                        ("wait".into(), c.wait.into()),
+                        ("value".into(), c.value.into()),
+                    ])
+                });
+                fields.push(("candidates".into(), Value::Arr(candidates.collect())));
+            }
+            out.push_str(&Value::Obj(fields).to_compact());
+            out.push('\n');
+        }
+        out
+    }
+}